@@ -1,8 +1,10 @@
 use std::{
-    ffi::{c_char, c_void},
+    env,
+    ffi::{c_char, c_void, OsString},
     mem,
     mem::MaybeUninit,
-    path::{Path, PathBuf}
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError}
 };
 
 use bitflags::bitflags;
@@ -11,7 +13,7 @@ use libloading::Library;
 use crate::{
     result_from_ffi, sys,
     utils::{assert_size_and_align, define_handle},
-    RpsResult
+    ErrorContext, RpsError, RpsResult
 };
 
 pub type Bool = i32;
@@ -117,7 +119,10 @@ assert_size_and_align!(DeviceCreateInfo, sys::RpsDeviceCreateInfo);
 #[inline]
 pub unsafe fn device_create(create_info: &DeviceCreateInfo) -> RpsResult<Device> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsDeviceCreate(create_info as *const DeviceCreateInfo as _, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsDeviceCreate(create_info as *const DeviceCreateInfo as _, &mut result as *mut _ as *mut _),
+        "rpsDeviceCreate"
+    )?;
     Ok(result.assume_init())
 }
 
@@ -141,6 +146,16 @@ impl DiagLogLevel {
     pub const ERROR: Self = Self(sys::RpsDiagLogLevel_RPS_DIAG_ERROR as _);
     pub const FATAL: Self = Self(sys::RpsDiagLogLevel_RPS_DIAG_FATAL as _);
     pub const COUNT: Self = Self(sys::RpsDiagLogLevel_RPS_DIAG_COUNT as _);
+
+    #[inline]
+    pub fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    #[inline]
+    pub fn into_raw(self) -> u32 {
+        self.0
+    }
 }
 
 #[inline]
@@ -200,6 +215,7 @@ impl TypeInfo {
 pub struct TypeId(u32);
 
 impl TypeId {
+    pub const OPAQUE: Self = Self(sys::RpsBuiltInTypeIds_RPS_TYPE_OPAQUE as _);
     pub const BOOL: Self = Self(sys::RpsBuiltInTypeIds_RPS_TYPE_BUILT_IN_BOOL as _);
     pub const INT8: Self = Self(sys::RpsBuiltInTypeIds_RPS_TYPE_BUILT_IN_INT8 as _);
     pub const UINT8: Self = Self(sys::RpsBuiltInTypeIds_RPS_TYPE_BUILT_IN_UINT8 as _);
@@ -218,12 +234,12 @@ impl TypeId {
     pub const BUFFER_VIEW: Self = Self(sys::RpsRuntimeBuiltInTypeIds_RPS_TYPE_BUFFER_VIEW as _);
 
     #[inline]
-    pub fn from_raw(raw: u32) -> Self {
+    pub const fn from_raw(raw: u32) -> Self {
         Self(raw)
     }
 
     #[inline]
-    pub fn into_raw(self) -> u32 {
+    pub const fn into_raw(self) -> u32 {
         self.0
     }
 }
@@ -311,7 +327,7 @@ pub type PfnRpslDynLibInit = sys::PFN_rpslDynLibInit;
 
 #[inline]
 pub unsafe fn rpsl_dynamic_library_init(pfn_dyn_lib_init: PfnRpslDynLibInit) -> RpsResult<()> {
-    result_from_ffi(sys::rpsRpslDynamicLibraryInit(pfn_dyn_lib_init))
+    result_from_ffi(sys::rpsRpslDynamicLibraryInit(pfn_dyn_lib_init), "rpsRpslDynamicLibraryInit")
 }
 
 #[inline]
@@ -340,12 +356,57 @@ fn jit_lib_name() -> &'static Path {
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 fn jit_lib_name() -> &'static Path {
-    todo!("Not supported at the moment")
+    Path::new("librps-jit.so")
 }
 
 #[cfg(target_os = "macos")]
 fn jit_lib_name() -> &'static Path {
-    todo!("Not supported at the moment")
+    Path::new("librps-jit.dylib")
+}
+
+pub const JIT_PATH_ENV_VAR: &str = "RPS_JIT_PATH";
+
+fn jit_lib_path_in(path: PathBuf) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path);
+    }
+
+    if path.is_dir() {
+        return Some(path.join(jit_lib_name())).filter(|path| path.is_file());
+    }
+
+    None
+}
+
+pub fn jit_lib_path(path: Option<PathBuf>) -> RpsResult<PathBuf> {
+    let exe_dir = env::current_exe().ok().and_then(|exe| Some(exe.parent()?.to_owned()));
+    jit_lib_path_from(path, env::var_os(JIT_PATH_ENV_VAR), exe_dir)
+}
+
+// Same lookup as `jit_lib_path`, with the environment variable and executable directory passed in.
+pub fn jit_lib_path_from(path: Option<PathBuf>, env_path: Option<OsString>, exe_dir: Option<PathBuf>) -> RpsResult<PathBuf> {
+    if let Some(path) = path {
+        return jit_lib_path_in(path.clone()).ok_or_else(|| RpsError::FileNotFound(ErrorContext::new("jit_lib_path")).with_name(path.display().to_string()));
+    }
+
+    if let Some(path) = env_path.filter(|path| !path.is_empty()).and_then(|path| jit_lib_path_in(path.into())) {
+        return Ok(path);
+    }
+
+    if let Some(path) = exe_dir.and_then(jit_lib_path_in) {
+        return Ok(path);
+    }
+
+    Ok(jit_lib_name().to_owned())
+}
+
+unsafe fn jit_lib_proc<T: Copy>(library: &Library, name: &'static [u8]) -> RpsResult<T> {
+    let symbol = library.get::<T>(name).map_err(|error| {
+        RpsError::InvalidFileFormat(ErrorContext::new("JITLibrary::new"))
+            .with_name(String::from_utf8_lossy(&name[..name.len() - 1]))
+            .with_source(error)
+    })?;
+    Ok(*symbol)
 }
 
 pub struct JITLibrary {
@@ -355,29 +416,27 @@ pub struct JITLibrary {
     jit_shutdown: PfnJITShutdown,
     jit_load: PfnJITLoad,
     jit_unload: PfnJITUnload,
-    jit_get_entry_point: PfnJITGetEntryPoint
+    jit_get_entry_point: PfnJITGetEntryPoint,
+
+    started: Mutex<bool>
 }
 
 impl JITLibrary {
-    pub fn new(path: Option<PathBuf>) -> Result<Self, libloading::Error> {
-        let total_path = if let Some(total_path) = path {
-            if total_path.is_file() {
-                total_path
-            } else {
-                total_path.join(jit_lib_name())
-            }
-        } else {
-            jit_lib_name().to_owned()
-        };
+    pub fn new(path: Option<PathBuf>) -> RpsResult<Self> {
+        let total_path = jit_lib_path(path)?;
 
-        let library = unsafe { Library::new(total_path) }?;
+        let library = unsafe { Library::new(&total_path) }.map_err(|error| {
+            RpsError::FileNotFound(ErrorContext::new("JITLibrary::new"))
+                .with_name(total_path.display().to_string())
+                .with_source(error)
+        })?;
 
         unsafe {
-            let jit_startup = mem::transmute(library.get::<PfnJITStartup>(JIT_PROC_NAME_STARTUP)?.into_raw().into_raw());
-            let jit_shutdown = mem::transmute(library.get::<PfnJITShutdown>(JIT_PROC_NAME_SHUTDOWN)?.into_raw().into_raw());
-            let jit_load = mem::transmute(library.get::<PfnJITLoad>(JIT_PROC_NAME_LOAD)?.into_raw().into_raw());
-            let jit_unload = mem::transmute(library.get::<PfnJITUnload>(JIT_PROC_NAME_UNLOAD)?.into_raw().into_raw());
-            let jit_get_entry_point = mem::transmute(library.get::<PfnJITGetEntryPoint>(JIT_PROC_NAME_GETENTRYPOINT)?.into_raw().into_raw());
+            let jit_startup = jit_lib_proc(&library, JIT_PROC_NAME_STARTUP)?;
+            let jit_shutdown = jit_lib_proc(&library, JIT_PROC_NAME_SHUTDOWN)?;
+            let jit_load = jit_lib_proc(&library, JIT_PROC_NAME_LOAD)?;
+            let jit_unload = jit_lib_proc(&library, JIT_PROC_NAME_UNLOAD)?;
+            let jit_get_entry_point = jit_lib_proc(&library, JIT_PROC_NAME_GETENTRYPOINT)?;
 
             Ok(Self {
                 _library: library,
@@ -386,25 +445,42 @@ impl JITLibrary {
                 jit_shutdown,
                 jit_load,
                 jit_unload,
-                jit_get_entry_point
+                jit_get_entry_point,
+
+                started: Mutex::new(false)
             })
         }
     }
 
+    // Only a successful startup is remembered, so a failed one can be retried.
     #[inline]
     pub unsafe fn startup(&self, argc: i32, args: *const *const c_char) -> i32 {
-        (self.jit_startup)(argc, args)
+        let mut started = self.started.lock().unwrap_or_else(PoisonError::into_inner);
+        if *started {
+            return sys::RpsResult_RPS_OK;
+        }
+
+        let result = (self.jit_startup)(argc, args);
+        *started = result == sys::RpsResult_RPS_OK;
+        result
     }
 
     #[inline]
-    pub unsafe fn shutdown(&self) {
-        (self.jit_shutdown)();
+    pub unsafe fn shutdown(&mut self) {
+        if mem::take(self.started.get_mut().unwrap_or_else(PoisonError::into_inner)) {
+            (self.jit_shutdown)();
+        }
+    }
+
+    #[inline]
+    pub fn is_started(&self) -> bool {
+        *self.started.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
     #[inline]
     pub unsafe fn load(&self, name: *const c_char) -> RpsResult<JITModule> {
         let mut result = MaybeUninit::uninit();
-        result_from_ffi((self.jit_load)(name, &mut result as *mut _ as *mut _))?;
+        result_from_ffi((self.jit_load)(name, &mut result as *mut _ as *mut _), "RpsJITLoad")?;
         Ok(result.assume_init())
     }
 
@@ -416,7 +492,14 @@ impl JITLibrary {
     #[inline]
     pub unsafe fn get_entry_point(&self, jit_module: JITModule, symbol_name: *const c_char) -> RpsResult<u64> {
         let mut result = MaybeUninit::uninit();
-        result_from_ffi((self.jit_get_entry_point)(jit_module, symbol_name, &mut result as *mut _ as *mut _))?;
+        result_from_ffi((self.jit_get_entry_point)(jit_module, symbol_name, &mut result as *mut _ as *mut _), "RpsJITGetEntryPoint")?;
         Ok(result.assume_init())
     }
 }
+
+impl Drop for JITLibrary {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { self.shutdown() };
+    }
+}
@@ -1,7 +1,9 @@
 use std::{
     error::Error,
+    ffi::{c_char, CStr},
     fmt::{Debug, Display, Formatter},
-    mem, slice
+    mem,
+    sync::Arc
 };
 
 use crate::sys;
@@ -42,17 +44,31 @@ impl Result {
 }
 
 impl Result {
+    #[inline]
+    pub fn from_raw(raw: i32) -> Self {
+        Self(raw)
+    }
+
     #[inline]
     pub fn into_raw(self) -> i32 {
         self.0
     }
+
+    #[inline]
+    pub fn name(self) -> &'static str {
+        let name = unsafe { sys::rpsResultGetName(mem::transmute(self)) };
+        if name.is_null() {
+            return "RPS_ERROR_UNKNOWN";
+        }
+
+        unsafe { CStr::from_ptr(name) }.to_str().unwrap_or("RPS_ERROR_UNKNOWN")
+    }
 }
 
 impl Debug for Result {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let name = unsafe { sys::rpsResultGetName(mem::transmute(*self)) };
-        write!(f, "{}", unsafe { std::str::from_utf8_unchecked(slice::from_raw_parts(name.cast(), libc::strlen(name))) })
+        f.write_str(self.name())
     }
 }
 
@@ -65,13 +81,188 @@ impl Display for Result {
 
 impl Error for Result {}
 
-pub type RpsResult<T> = std::result::Result<T, Result>;
+#[derive(Clone, Debug, Default)]
+pub struct ErrorContext {
+    pub api: &'static str,
+    pub name: Option<String>,
+    pub source: Option<Arc<dyn Error + Send + Sync + 'static>>
+}
+
+impl ErrorContext {
+    #[inline]
+    pub fn new(api: &'static str) -> Self {
+        Self { api, name: None, source: None }
+    }
+}
+
+macro_rules! define_errors {
+    ($($variant: ident => $code: ident),* $(,)?) => {
+        #[non_exhaustive]
+        #[derive(Clone, Debug)]
+        pub enum RpsError {
+            $($variant(ErrorContext),)*
+            Unknown(Result, ErrorContext)
+        }
+
+        impl RpsError {
+            #[inline]
+            pub fn from_code(code: Result, context: ErrorContext) -> Option<Self> {
+                match code {
+                    Result::OK => None,
+                    $(Result::$code => Some(Self::$variant(context)),)*
+                    _ => Some(Self::Unknown(code, context))
+                }
+            }
+
+            #[inline]
+            pub fn code(&self) -> Result {
+                match self {
+                    $(Self::$variant(_) => Result::$code,)*
+                    Self::Unknown(code, _) => *code
+                }
+            }
+
+            #[inline]
+            pub fn context(&self) -> &ErrorContext {
+                match self {
+                    $(Self::$variant(context) => context,)*
+                    Self::Unknown(_, context) => context
+                }
+            }
+
+            #[inline]
+            fn context_mut(&mut self) -> &mut ErrorContext {
+                match self {
+                    $(Self::$variant(context) => context,)*
+                    Self::Unknown(_, context) => context
+                }
+            }
+        }
+    };
+}
+
+define_errors! {
+    Unspecified => UNSPECIFIED,
+    UnrecognizedCommand => UNRECOGNIZED_COMMAND,
+    InvalidArguments => INVALID_ARGUMENTS,
+    InvalidData => INVALID_DATA,
+    InvalidOperation => INVALID_OPERATION,
+    OutOfMemory => OUT_OF_MEMORY,
+    FileNotFound => FILE_NOT_FOUND,
+    InvalidFileFormat => INVALID_FILE_FORMAT,
+    UnsupportedVersionTooOld => UNSUPPORTED_VERSION_TOO_OLD,
+    UnsupportedVersionTooNew => UNSUPPORTED_VERSION_TOO_NEW,
+    UnknownNode => UNKNOWN_NODE,
+    IndexOutOfBounds => INDEX_OUT_OF_BOUNDS,
+    CommandAlreadyFinal => COMMAND_ALREADY_FINAL,
+    InteropDataLayoutMismatch => INTEROP_DATA_LAYOUT_MISMATCH,
+    KeyNotFound => KEY_NOT_FOUND,
+    KeyDuplicated => KEY_DUPLICATED,
+    NotImplemented => NOT_IMPLEMENTED,
+    IntegerOverflow => INTEGER_OVERFLOW,
+    RangeOverlapping => RANGE_OVERLAPPING,
+    ValidationFailed => VALIDATION_FAILED,
+    InvalidProgram => INVALID_PROGRAM,
+    UnsupportedModuleVersion => UNSUPPORTED_MODULE_VERSION,
+    TypeMismatch => TYPE_MISMATCH,
+    NotSupported => NOT_SUPPORTED,
+    RuntimeApiError => RUNTIME_API_ERROR,
+    InternalError => INTERNAL_ERROR
+}
+
+impl RpsError {
+    #[inline]
+    pub fn raw_code(&self) -> i32 {
+        self.code().into_raw()
+    }
+
+    #[inline]
+    pub fn api(&self) -> &'static str {
+        self.context().api
+    }
+
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.context().name.as_deref()
+    }
+
+    #[inline]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.context_mut().name = Some(name.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.context_mut().source = Some(Arc::new(source));
+        self
+    }
+}
+
+impl Display for RpsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed with {}", self.api(), self.code())?;
+        if let Some(name) = self.name() {
+            write!(f, " ({name})")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RpsError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.context().source.as_deref().map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl TryFrom<Result> for RpsError {
+    type Error = Result;
+
+    #[inline]
+    fn try_from(code: Result) -> std::result::Result<Self, Result> {
+        Self::from_code(code, ErrorContext::default()).ok_or(code)
+    }
+}
+
+impl TryFrom<i32> for RpsError {
+    type Error = Result;
+
+    #[inline]
+    fn try_from(raw: i32) -> std::result::Result<Self, Result> {
+        Self::try_from(Result::from_raw(raw))
+    }
+}
+
+impl From<&RpsError> for Result {
+    #[inline]
+    fn from(error: &RpsError) -> Self {
+        error.code()
+    }
+}
+
+impl From<RpsError> for Result {
+    #[inline]
+    fn from(error: RpsError) -> Self {
+        error.code()
+    }
+}
+
+pub type RpsResult<T> = std::result::Result<T, RpsError>;
+
+#[inline]
+pub(crate) unsafe fn with_c_name(error: RpsError, name: *const c_char) -> RpsError {
+    if name.is_null() {
+        error
+    } else {
+        error.with_name(CStr::from_ptr(name).to_string_lossy())
+    }
+}
 
 #[inline]
-pub unsafe fn result_from_ffi(result: sys::RpsResult) -> RpsResult<()> {
-    let result: Result = mem::transmute(result);
-    match result {
-        Result::OK => Ok(()),
-        _ => Err(result)
+pub unsafe fn result_from_ffi(result: sys::RpsResult, api: &'static str) -> RpsResult<()> {
+    match RpsError::from_code(mem::transmute(result), ErrorContext::new(api)) {
+        None => Ok(()),
+        Some(error) => Err(error)
     }
 }
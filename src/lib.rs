@@ -7,6 +7,7 @@ mod sys {
 }
 
 mod runtime;
+pub mod safe;
 mod utils;
 
 pub use runtime::*;
@@ -7,7 +7,7 @@ use std::{
 use bitflags::bitflags;
 
 use crate::{
-    core::{Result, RpsResult},
+    core::{with_c_name, ErrorContext, Result, RpsError, RpsResult},
     result_from_ffi, sys,
     utils::{assert_size_and_align, define_handle},
     AccessAttr, Bool, ClearValue, CmdRenderTargetInfo, CmdViewportInfo, Constant, Device, DeviceCreateInfo, Format, Index32, NodeDeclId, NodeId, ParamId, RandomNumberGenerator,
@@ -199,7 +199,7 @@ assert_size_and_align!(RenderGraphSignatureDesc, sys::RpsRenderGraphSignatureDes
 
 #[inline]
 pub unsafe fn cmd_callback_report_error(context: *const CmdCallbackContext, error_code: Result) -> RpsResult<()> {
-    result_from_ffi(sys::rpsCmdCallbackReportError(context.cast(), mem::transmute(error_code)))
+    result_from_ffi(sys::rpsCmdCallbackReportError(context.cast(), mem::transmute(error_code)), "rpsCmdCallbackReportError")
 }
 
 bitflags! {
@@ -239,7 +239,7 @@ assert_size_and_align!(RenderGraphUpdateInfo, sys::RpsRenderGraphUpdateInfo);
 pub const MAX_QUEUED_FRAMES: usize = 16;
 pub const GPU_COMPLETED_FRAME_INDEX_NONE: u64 = u64::MAX;
 
-pub type PfnRenderGraphPhaseRun = Option<unsafe extern "C" fn(render_graph: RenderGraph, update_info: *const RenderGraphUpdateInfo, phase: RenderGraphPhase)>;
+pub type PfnRenderGraphPhaseRun = Option<unsafe extern "C" fn(render_graph: RenderGraph, update_info: *const RenderGraphUpdateInfo, phase: RenderGraphPhase) -> Result>;
 pub type PfnRenderGraphPhaseDestroy = Option<unsafe extern "C" fn(phase: RenderGraphPhase)>;
 
 #[repr(C)]
@@ -339,9 +339,9 @@ impl Default for NullRuntimeDeviceCreateInfo {
 assert_size_and_align!(NullRuntimeDeviceCreateInfo, sys::RpsNullRuntimeDeviceCreateInfo);
 
 #[inline]
-pub unsafe fn null_runtime_device_create(create_info: *const DeviceCreateInfo) -> RpsResult<Device> {
+pub unsafe fn null_runtime_device_create(create_info: *const NullRuntimeDeviceCreateInfo) -> RpsResult<Device> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsNullRuntimeDeviceCreate(create_info.cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsNullRuntimeDeviceCreate(create_info.cast(), &mut result as *mut _ as *mut _), "rpsNullRuntimeDeviceCreate")?;
     Ok(result.assume_init())
 }
 
@@ -365,14 +365,20 @@ assert_size_and_align!(ProgramCreateInfo, sys::RpsProgramCreateInfo);
 #[inline]
 pub unsafe fn rpsl_entry_get_signature_desc(rpsl_entry: RpslEntry) -> RpsResult<RenderGraphSignatureDesc> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsRpslEntryGetSignatureDesc(rpsl_entry.into_raw().cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsRpslEntryGetSignatureDesc(rpsl_entry.into_raw().cast(), &mut result as *mut _ as *mut _),
+        "rpsRpslEntryGetSignatureDesc"
+    )?;
     Ok(result.assume_init())
 }
 
 #[inline]
 pub unsafe fn program_create(device: Device, create_info: *const ProgramCreateInfo) -> RpsResult<Subprogram> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsProgramCreate(device.into_raw().cast(), create_info.cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsProgramCreate(device.into_raw().cast(), create_info.cast(), &mut result as *mut _ as *mut _),
+        "rpsProgramCreate"
+    )?;
     Ok(result.assume_init())
 }
 
@@ -383,12 +389,16 @@ pub unsafe fn program_destroy(program: Subprogram) {
 
 #[inline]
 pub unsafe fn program_bind_node_callback(program: Subprogram, name: *const c_char, callback: *const CmdCallback) -> RpsResult<()> {
-    result_from_ffi(sys::rpsProgramBindNodeCallback(program.into_raw().cast(), name, callback.cast()))
+    result_from_ffi(sys::rpsProgramBindNodeCallback(program.into_raw().cast(), name, callback.cast()), "rpsProgramBindNodeCallback").map_err(|error| with_c_name(error, name))
 }
 
 #[inline]
 pub unsafe fn program_bind_node_subprogram(program: Subprogram, name: *const c_char, subprogram: Subprogram) -> RpsResult<()> {
-    result_from_ffi(sys::rpsProgramBindNodeSubprogram(program.into_raw().cast(), name, subprogram.into_raw().cast()))
+    result_from_ffi(
+        sys::rpsProgramBindNodeSubprogram(program.into_raw().cast(), name, subprogram.into_raw().cast()),
+        "rpsProgramBindNodeSubprogram"
+    )
+    .map_err(|error| with_c_name(error, name))
 }
 
 #[repr(C)]
@@ -443,13 +453,16 @@ assert_size_and_align!(RenderGraphCreateInfo, sys::RpsRenderGraphCreateInfo);
 #[inline]
 pub unsafe fn render_graph_create(device: Device, create_info: *const RenderGraphCreateInfo) -> RpsResult<RenderGraph> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsRenderGraphCreate(device.into_raw().cast(), create_info.cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsRenderGraphCreate(device.into_raw().cast(), create_info.cast(), &mut result as *mut _ as *mut _),
+        "rpsRenderGraphCreate"
+    )?;
     Ok(result.assume_init())
 }
 
 #[inline]
 pub unsafe fn render_graph_update(render_graph: RenderGraph, update_info: *const RenderGraphUpdateInfo) -> RpsResult<()> {
-    result_from_ffi(sys::rpsRenderGraphUpdate(render_graph.into_raw().cast(), update_info.cast()))
+    result_from_ffi(sys::rpsRenderGraphUpdate(render_graph.into_raw().cast(), update_info.cast()), "rpsRenderGraphUpdate")
 }
 
 #[inline]
@@ -475,10 +488,11 @@ pub unsafe fn render_graph_allocate_data_for_type<T>(render_graph_builder: Rende
 }
 
 #[inline]
-pub unsafe fn render_graph_allocate_data_for_type_with_len<T>(render_graph_builder: RenderGraphBuilder, len: usize) -> *mut T {
-    render_graph_allocate_data_aligned(render_graph_builder, mem::size_of::<T>(), mem::align_of::<T>() * len)
-        .cast_mut()
-        .cast()
+pub unsafe fn render_graph_allocate_data_for_type_with_len<T>(render_graph_builder: RenderGraphBuilder, len: usize) -> RpsResult<*mut T> {
+    let size = mem::size_of::<T>()
+        .checked_mul(len)
+        .ok_or_else(|| RpsError::IntegerOverflow(ErrorContext::new("rpsRenderGraphAllocateDataAligned")))?;
+    Ok(render_graph_allocate_data_aligned(render_graph_builder, size, mem::align_of::<T>()).cast_mut().cast())
 }
 
 #[inline]
@@ -527,12 +541,10 @@ pub unsafe fn render_graph_add_node(
 #[inline]
 pub unsafe fn render_graph_get_resource_info(render_graph_builder: RenderGraphBuilder, resource_id: ResourceId, temporal_layer_index: u32) -> RpsResult<RuntimeResourceInfo> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsRenderGraphGetResourceInfo(
-        render_graph_builder.into_raw().cast(),
-        resource_id,
-        temporal_layer_index,
-        &mut result as *mut _ as *mut _
-    ))?;
+    result_from_ffi(
+        sys::rpsRenderGraphGetResourceInfo(render_graph_builder.into_raw().cast(), resource_id, temporal_layer_index, &mut result as *mut _ as *mut _),
+        "rpsRenderGraphGetResourceInfo"
+    )?;
     Ok(result.assume_init())
 }
 
@@ -544,13 +556,10 @@ pub unsafe fn render_graph_get_output_parameter_resource_infos(
     num_resources: u32,
     resource_infos: *mut RuntimeResourceInfo
 ) -> RpsResult<()> {
-    result_from_ffi(sys::rpsRenderGraphGetOutputParameterResourceInfos(
-        render_graph_builder.into_raw().cast(),
-        param_id,
-        array_offset,
-        num_resources,
-        resource_infos.cast()
-    ))
+    result_from_ffi(
+        sys::rpsRenderGraphGetOutputParameterResourceInfos(render_graph_builder.into_raw().cast(), param_id, array_offset, num_resources, resource_infos.cast()),
+        "rpsRenderGraphGetOutputParameterResourceInfos"
+    )
 }
 
 #[inline]
@@ -621,13 +630,19 @@ assert_size_and_align!(RenderGraphRecordCommandInfo, sys::RpsRenderGraphRecordCo
 #[inline]
 pub unsafe fn render_graph_get_batch_layout(render_graph: RenderGraph) -> RpsResult<RenderGraphBatchLayout> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsRenderGraphGetBatchLayout(render_graph.into_raw().cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsRenderGraphGetBatchLayout(render_graph.into_raw().cast(), &mut result as *mut _ as *mut _),
+        "rpsRenderGraphGetBatchLayout"
+    )?;
     Ok(result.assume_init())
 }
 
 #[inline]
 pub unsafe fn render_graph_record_commands(render_graph: RenderGraph, record_info: *const RenderGraphRecordCommandInfo) -> RpsResult<()> {
-    result_from_ffi(sys::rpsRenderGraphRecordCommands(render_graph.into_raw().cast(), record_info.cast()))
+    result_from_ffi(
+        sys::rpsRenderGraphRecordCommands(render_graph.into_raw().cast(), record_info.cast()),
+        "rpsRenderGraphRecordCommands"
+    )
 }
 
 pub const CMD_ID_INVALID: u32 = INDEX_NONE_U32;
@@ -740,11 +755,10 @@ bitflags! {
 #[inline]
 pub unsafe fn render_graph_get_diagnostics_info(render_graph: RenderGraph, diagnostic_flags: RenderGraphDiagnosticInfoFlags) -> RpsResult<RenderGraphDiagnosticInfo> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsRenderGraphGetDiagnosticInfo(
-        render_graph.into_raw().cast(),
-        &mut result as *mut _ as *mut _,
-        mem::transmute(diagnostic_flags)
-    ))?;
+    result_from_ffi(
+        sys::rpsRenderGraphGetDiagnosticInfo(render_graph.into_raw().cast(), &mut result as *mut _ as *mut _, mem::transmute(diagnostic_flags)),
+        "rpsRenderGraphGetDiagnosticInfo"
+    )?;
     Ok(result.assume_init())
 }
 
@@ -782,14 +796,14 @@ assert_size_and_align!(ResourceAccessInfo, sys::RpsResourceAccessInfo);
 #[inline]
 pub unsafe fn cmd_get_render_targets_info(context: *const CmdCallbackContext) -> RpsResult<CmdRenderTargetInfo> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsCmdGetRenderTargetsInfo(context.cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsCmdGetRenderTargetsInfo(context.cast(), &mut result as *mut _ as *mut _), "rpsCmdGetRenderTargetsInfo")?;
     Ok(result.assume_init())
 }
 
 #[inline]
 pub unsafe fn cmd_get_viewport_info(context: *const CmdCallbackContext) -> RpsResult<CmdViewportInfo> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsCmdGetViewportInfo(context.cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsCmdGetViewportInfo(context.cast(), &mut result as *mut _ as *mut _), "rpsCmdGetViewportInfo")?;
     Ok(result.assume_init())
 }
 
@@ -802,38 +816,37 @@ pub struct CmdRenderPassBeginInfo {
 #[inline]
 pub unsafe fn cmd_clone_context(context: *const CmdCallbackContext, cmd_buffer_for_derived_context: RuntimeCommandBuffer) -> RpsResult<*const CmdCallbackContext> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsCmdCloneContext(
-        context.cast(),
-        mem::transmute(cmd_buffer_for_derived_context),
-        &mut result as *mut _ as *mut _
-    ))?;
+    result_from_ffi(
+        sys::rpsCmdCloneContext(context.cast(), mem::transmute(cmd_buffer_for_derived_context), &mut result as *mut _ as *mut _),
+        "rpsCmdCloneContext"
+    )?;
     Ok(result.assume_init())
 }
 
 #[inline]
 pub unsafe fn cmd_begin_render_pass(context: *const CmdCallbackContext, begin_info: *const CmdRenderPassBeginInfo) -> RpsResult<()> {
-    result_from_ffi(sys::rpsCmdBeginRenderPass(context.cast(), begin_info.cast()))
+    result_from_ffi(sys::rpsCmdBeginRenderPass(context.cast(), begin_info.cast()), "rpsCmdBeginRenderPass")
 }
 
 #[inline]
 pub unsafe fn cmd_end_render_pass(context: *const CmdCallbackContext) -> RpsResult<()> {
-    result_from_ffi(sys::rpsCmdEndRenderPass(context.cast()))
+    result_from_ffi(sys::rpsCmdEndRenderPass(context.cast()), "rpsCmdEndRenderPass")
 }
 
 #[inline]
 pub unsafe fn cmd_set_command_buffer(context: *const CmdCallbackContext, cmd_buffer: RuntimeCommandBuffer) -> RpsResult<()> {
-    result_from_ffi(sys::rpsCmdSetCommandBuffer(context.cast(), mem::transmute(cmd_buffer)))
+    result_from_ffi(sys::rpsCmdSetCommandBuffer(context.cast(), mem::transmute(cmd_buffer)), "rpsCmdSetCommandBuffer")
 }
 
 #[inline]
 pub unsafe fn cmd_get_node_name(context: *const CmdCallbackContext, node_names: *mut *const c_char, node_name_length: *mut usize) -> RpsResult<()> {
-    result_from_ffi(sys::rpsCmdGetNodeName(context.cast(), node_names, node_name_length))
+    result_from_ffi(sys::rpsCmdGetNodeName(context.cast(), node_names, node_name_length), "rpsCmdGetNodeName")
 }
 
 #[inline]
 pub unsafe fn cmd_get_param_desc(context: *const CmdCallbackContext, param_id: ParamId) -> RpsResult<ParameterDesc> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsCmdGetParamDesc(context.cast(), param_id, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsCmdGetParamDesc(context.cast(), param_id, &mut result as *mut _ as *mut _), "rpsCmdGetParamDesc")?;
     Ok(result.assume_init())
 }
 
@@ -845,13 +858,19 @@ pub unsafe fn cmd_get_arg_resource_desc_array(
     resource_descs: *mut ResourceDesc,
     num_descs: u32
 ) -> RpsResult<()> {
-    result_from_ffi(sys::rpsCmdGetArgResourceDescArray(context.cast(), arg_index, src_array_offset, resource_descs.cast(), num_descs))
+    result_from_ffi(
+        sys::rpsCmdGetArgResourceDescArray(context.cast(), arg_index, src_array_offset, resource_descs.cast(), num_descs),
+        "rpsCmdGetArgResourceDescArray"
+    )
 }
 
 #[inline]
 pub unsafe fn cmd_get_arg_resource_desc(context: *const CmdCallbackContext, arg_index: ParamId) -> RpsResult<ResourceDesc> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsCmdGetArgResourceDesc(context.cast(), arg_index, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsCmdGetArgResourceDesc(context.cast(), arg_index, &mut result as *mut _ as *mut _),
+        "rpsCmdGetArgResourceDesc"
+    )?;
     Ok(result.assume_init())
 }
 
@@ -863,19 +882,43 @@ pub unsafe fn cmd_get_arg_resource_access_info_array(
     resource_access_infos: *mut ResourceAccessInfo,
     num_accessess: u32
 ) -> RpsResult<()> {
-    result_from_ffi(sys::rpsCmdGetArgResourceAccessInfoArray(
-        context.cast(),
-        arg_index,
-        src_array_offset,
-        resource_access_infos.cast(),
-        num_accessess
-    ))
+    result_from_ffi(
+        sys::rpsCmdGetArgResourceAccessInfoArray(context.cast(), arg_index, src_array_offset, resource_access_infos.cast(), num_accessess),
+        "rpsCmdGetArgResourceAccessInfoArray"
+    )
 }
 
 #[inline]
 pub unsafe fn cmd_get_arg_resource_access_info(context: *const CmdCallbackContext, arg_index: ParamId) -> RpsResult<ResourceAccessInfo> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsCmdGetArgResourceAccessInfo(context.cast(), arg_index, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsCmdGetArgResourceAccessInfo(context.cast(), arg_index, &mut result as *mut _ as *mut _),
+        "rpsCmdGetArgResourceAccessInfo"
+    )?;
+    Ok(result.assume_init())
+}
+
+#[inline]
+pub unsafe fn cmd_get_arg_runtime_resource_array(
+    context: *const CmdCallbackContext,
+    arg_index: ParamId,
+    src_array_offset: u32,
+    runtime_resources: *mut RuntimeResource,
+    num_runtime_resources: u32
+) -> RpsResult<()> {
+    result_from_ffi(
+        sys::rpsCmdGetArgRuntimeResourceArray(context.cast(), arg_index, src_array_offset, runtime_resources.cast(), num_runtime_resources),
+        "rpsCmdGetArgRuntimeResourceArray"
+    )
+}
+
+#[inline]
+pub unsafe fn cmd_get_arg_runtime_resource(context: *const CmdCallbackContext, arg_index: ParamId) -> RpsResult<RuntimeResource> {
+    let mut result = MaybeUninit::uninit();
+    result_from_ffi(
+        sys::rpsCmdGetArgRuntimeResource(context.cast(), arg_index, &mut result as *mut _ as *mut _),
+        "rpsCmdGetArgRuntimeResource"
+    )?;
     Ok(result.assume_init())
 }
 
@@ -908,5 +951,5 @@ assert_size_and_align!(RenderGraphExecuteInfo, sys::RpsRenderGraphExecuteInfo);
 
 #[inline]
 pub unsafe fn render_graph_execute(render_graph: RenderGraph, execute_info: *const RenderGraphExecuteInfo) -> RpsResult<()> {
-    result_from_ffi(sys::rpsRenderGraphExecute(render_graph.into_raw().cast(), execute_info.cast()))
+    result_from_ffi(sys::rpsRenderGraphExecute(render_graph.into_raw().cast(), execute_info.cast()), "rpsRenderGraphExecute")
 }
@@ -142,7 +142,7 @@ assert_size_and_align!(VKRuntimeDeviceCreateInfo, sys::RpsVKRuntimeDeviceCreateI
 #[inline]
 pub unsafe fn vk_runtime_device_create(create_info: *const VKRuntimeDeviceCreateInfo) -> RpsResult<Device> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsVKRuntimeDeviceCreate(create_info.cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsVKRuntimeDeviceCreate(create_info.cast(), &mut result as *mut _ as *mut _), "rpsVKRuntimeDeviceCreate")?;
     Ok(result.assume_init())
 }
 
@@ -194,13 +194,19 @@ pub unsafe fn vk_get_cmd_arg_image_view_array(
     image_views: *mut vk::ImageView,
     num_image_views: u32
 ) -> RpsResult<()> {
-    result_from_ffi(sys::rpsVKGetCmdArgImageViewArray(context.cast(), arg_index, src_array_offset, image_views, num_image_views))
+    result_from_ffi(
+        sys::rpsVKGetCmdArgImageViewArray(context.cast(), arg_index, src_array_offset, image_views, num_image_views),
+        "rpsVKGetCmdArgImageViewArray"
+    )
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_arg_image_view(context: *const CmdCallbackContext, arg_index: u32) -> RpsResult<vk::ImageView> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsVKGetCmdArgImageView(context.cast(), arg_index, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsVKGetCmdArgImageView(context.cast(), arg_index, &mut result as *mut _ as *mut _),
+        "rpsVKGetCmdArgImageView"
+    )?;
     Ok(result.assume_init())
 }
 
@@ -221,24 +227,34 @@ pub unsafe fn vk_get_cmd_arg_image_view_info_array(
     image_view_infos: *mut VkImageViewInfo,
     num_image_view_infos: u32
 ) -> RpsResult<()> {
-    result_from_ffi(sys::rpsVKGetCmdArgImageViewInfoArray(
-        context.cast(),
-        arg_index,
-        src_array_offset,
-        image_view_infos.cast(),
-        num_image_view_infos
-    ))
+    result_from_ffi(
+        sys::rpsVKGetCmdArgImageViewInfoArray(context.cast(), arg_index, src_array_offset, image_view_infos.cast(), num_image_view_infos),
+        "rpsVKGetCmdArgImageViewInfoArray"
+    )
+}
+
+#[inline]
+pub unsafe fn vk_get_cmd_arg_image_view_info(context: *const CmdCallbackContext, arg_index: u32) -> RpsResult<VkImageViewInfo> {
+    let mut result = VkImageViewInfo::default();
+    result_from_ffi(
+        sys::rpsVKGetCmdArgImageViewInfo(context.cast(), arg_index, (&mut result as *mut VkImageViewInfo).cast()),
+        "rpsVKGetCmdArgImageViewInfo"
+    )?;
+    Ok(result)
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_arg_image_array(context: *const CmdCallbackContext, arg_index: u32, src_array_offset: u32, images: *mut vk::Image, num_images: u32) -> RpsResult<()> {
-    result_from_ffi(sys::rpsVKGetCmdArgImageArray(context.cast(), arg_index, src_array_offset, images, num_images))
+    result_from_ffi(
+        sys::rpsVKGetCmdArgImageArray(context.cast(), arg_index, src_array_offset, images, num_images),
+        "rpsVKGetCmdArgImageArray"
+    )
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_arg_image(context: *const CmdCallbackContext, arg_index: u32) -> RpsResult<vk::Image> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsVKGetCmdArgImage(context.cast(), arg_index, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsVKGetCmdArgImage(context.cast(), arg_index, &mut result as *mut _ as *mut _), "rpsVKGetCmdArgImage")?;
     Ok(result.assume_init())
 }
 
@@ -250,25 +266,34 @@ pub unsafe fn vk_get_cmd_arg_buffer_view_array(
     buffer_views: *mut vk::BufferView,
     num_buffer_views: u32
 ) -> RpsResult<()> {
-    result_from_ffi(sys::rpsVKGetCmdArgBufferViewArray(context.cast(), arg_index, src_array_offset, buffer_views, num_buffer_views))
+    result_from_ffi(
+        sys::rpsVKGetCmdArgBufferViewArray(context.cast(), arg_index, src_array_offset, buffer_views, num_buffer_views),
+        "rpsVKGetCmdArgBufferViewArray"
+    )
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_arg_buffer_view(context: *const CmdCallbackContext, arg_index: u32) -> RpsResult<vk::BufferView> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsVKGetCmdArgBufferView(context.cast(), arg_index, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsVKGetCmdArgBufferView(context.cast(), arg_index, &mut result as *mut _ as *mut _),
+        "rpsVKGetCmdArgBufferView"
+    )?;
     Ok(result.assume_init())
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_arg_buffer_array(context: *const CmdCallbackContext, arg_index: u32, src_array_offset: u32, buffers: *mut vk::Buffer, num_buffers: u32) -> RpsResult<()> {
-    result_from_ffi(sys::rpsVKGetCmdArgBufferArray(context.cast(), arg_index, src_array_offset, buffers, num_buffers))
+    result_from_ffi(
+        sys::rpsVKGetCmdArgBufferArray(context.cast(), arg_index, src_array_offset, buffers, num_buffers),
+        "rpsVKGetCmdArgBufferArray"
+    )
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_arg_buffer(context: *const CmdCallbackContext, arg_index: u32) -> RpsResult<vk::Buffer> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsVKGetCmdArgBuffer(context.cast(), arg_index, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsVKGetCmdArgBuffer(context.cast(), arg_index, &mut result as *mut _ as *mut _), "rpsVKGetCmdArgBuffer")?;
     Ok(result.assume_init())
 }
 
@@ -290,20 +315,26 @@ pub unsafe fn vk_get_cmd_arg_gpu_memory_array(
     memory_ranges: *mut VkDeviceMemoryRange,
     num_ranges: u32
 ) -> RpsResult<()> {
-    result_from_ffi(sys::rpsVKGetCmdArgGpuMemoryArray(context.cast(), arg_index, src_array_offset, memory_ranges.cast(), num_ranges))
+    result_from_ffi(
+        sys::rpsVKGetCmdArgGpuMemoryArray(context.cast(), arg_index, src_array_offset, memory_ranges.cast(), num_ranges),
+        "rpsVKGetCmdArgGpuMemoryArray"
+    )
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_arg_gpu_memory(context: *const CmdCallbackContext, arg_index: u32) -> RpsResult<VkDeviceMemoryRange> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsVKGetCmdArgGpuMemory(context.cast(), arg_index, &mut result as *mut _ as *mut _))?;
+    result_from_ffi(
+        sys::rpsVKGetCmdArgGpuMemory(context.cast(), arg_index, &mut result as *mut _ as *mut _),
+        "rpsVKGetCmdArgGpuMemory"
+    )?;
     Ok(result.assume_init())
 }
 
 #[inline]
 pub unsafe fn vk_get_cmd_render_pass(context: *const CmdCallbackContext) -> RpsResult<vk::RenderPass> {
     let mut result = MaybeUninit::uninit();
-    result_from_ffi(sys::rpsVKGetCmdRenderPass(context.cast(), &mut result as *mut _ as *mut _))?;
+    result_from_ffi(sys::rpsVKGetCmdRenderPass(context.cast(), &mut result as *mut _ as *mut _), "rpsVKGetCmdRenderPass")?;
     Ok(result.assume_init())
 }
 
@@ -0,0 +1,181 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    ffi::c_void,
+    mem, ptr,
+    sync::atomic::{AtomicUsize, Ordering}
+};
+
+use crate::Allocator;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AllocationStats {
+    pub live_bytes: usize,
+    pub peak_bytes: usize,
+    pub live_allocations: usize
+}
+
+pub struct CountingAllocator<A: ?Sized> {
+    live_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    live_allocations: AtomicUsize,
+    inner: A
+}
+
+impl<A> CountingAllocator<A> {
+    #[inline]
+    pub const fn new(inner: A) -> Self {
+        Self {
+            live_bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+            live_allocations: AtomicUsize::new(0),
+            inner
+        }
+    }
+}
+
+impl<A: ?Sized> CountingAllocator<A> {
+    #[inline]
+    pub fn stats(&self) -> AllocationStats {
+        AllocationStats {
+            live_bytes: self.live_bytes.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed),
+            live_allocations: self.live_allocations.load(Ordering::Relaxed)
+        }
+    }
+
+    #[inline]
+    fn on_alloc(&self, size: usize) {
+        let live_bytes = self.live_bytes.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak_bytes.fetch_max(live_bytes, Ordering::Relaxed);
+        self.live_allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn on_dealloc(&self, size: usize) {
+        self.live_bytes.fetch_sub(size, Ordering::Relaxed);
+        self.live_allocations.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A: GlobalAlloc + ?Sized> GlobalAlloc for CountingAllocator<A> {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.on_dealloc(layout.size());
+    }
+}
+
+#[repr(C)]
+struct AllocationHeader {
+    size: usize,
+    alignment: usize
+}
+
+impl AllocationHeader {
+    #[inline]
+    fn layout(size: usize, alignment: usize) -> Option<(Layout, usize)> {
+        let alignment = alignment.max(mem::align_of::<Self>());
+        let offset = mem::size_of::<Self>().checked_next_multiple_of(alignment)?;
+        let layout = Layout::from_size_align(offset.checked_add(size)?, alignment).ok()?;
+        Some((layout, offset))
+    }
+}
+
+struct HostAllocatorContext {
+    inner: Box<CountingAllocator<dyn GlobalAlloc + Send + Sync>>
+}
+
+impl HostAllocatorContext {
+    unsafe fn alloc(&self, size: usize, alignment: usize) -> *mut c_void {
+        let Some((layout, offset)) = AllocationHeader::layout(size, alignment) else {
+            return ptr::null_mut();
+        };
+
+        let base = self.inner.alloc(layout);
+        if base.is_null() {
+            return ptr::null_mut();
+        }
+
+        let buffer = base.add(offset);
+        buffer.cast::<AllocationHeader>().sub(1).write(AllocationHeader { size, alignment: layout.align() });
+        buffer.cast()
+    }
+
+    unsafe fn free(&self, buffer: *mut c_void) {
+        if buffer.is_null() {
+            return;
+        }
+
+        let header = buffer.cast::<AllocationHeader>().sub(1).read();
+        let (layout, offset) = AllocationHeader::layout(header.size, header.alignment).unwrap_unchecked();
+        self.inner.dealloc(buffer.cast::<u8>().sub(offset), layout);
+    }
+
+    unsafe fn realloc(&self, old_buffer: *mut c_void, new_size: usize, alignment: usize) -> *mut c_void {
+        let new_buffer = self.alloc(new_size, alignment);
+        if !new_buffer.is_null() && !old_buffer.is_null() {
+            let old_size = old_buffer.cast::<AllocationHeader>().sub(1).read().size;
+            ptr::copy_nonoverlapping(old_buffer.cast::<u8>(), new_buffer.cast::<u8>(), old_size.min(new_size));
+            self.free(old_buffer);
+        }
+        new_buffer
+    }
+}
+
+unsafe extern "C" fn host_alloc(user_context: *mut c_void, size: usize, alignment: usize) -> *mut c_void {
+    (*user_context.cast::<HostAllocatorContext>()).alloc(size, alignment)
+}
+
+unsafe extern "C" fn host_realloc(user_context: *mut c_void, old_buffer: *mut c_void, _old_size: usize, new_size: usize, alignment: usize) -> *mut c_void {
+    (*user_context.cast::<HostAllocatorContext>()).realloc(old_buffer, new_size, alignment)
+}
+
+unsafe extern "C" fn host_free(user_context: *mut c_void, buffer: *mut c_void) {
+    (*user_context.cast::<HostAllocatorContext>()).free(buffer)
+}
+
+pub struct HostAllocator {
+    context: Box<HostAllocatorContext>
+}
+
+impl HostAllocator {
+    #[inline]
+    pub fn new<A: GlobalAlloc + Send + Sync + 'static>(allocator: A) -> Self {
+        Self {
+            context: Box::new(HostAllocatorContext {
+                inner: Box::new(CountingAllocator::new(allocator))
+            })
+        }
+    }
+
+    #[inline]
+    pub fn allocator(&self) -> Allocator {
+        Allocator {
+            pfn_alloc: Some(host_alloc),
+            pfn_realloc: Some(host_realloc),
+            pfn_free: Some(host_free),
+            context: &*self.context as *const HostAllocatorContext as *mut c_void
+        }
+    }
+
+    #[inline]
+    pub fn stats(&self) -> AllocationStats {
+        self.context.inner.stats()
+    }
+}
+
+impl Default for HostAllocator {
+    #[inline]
+    fn default() -> Self {
+        Self::new(System)
+    }
+}
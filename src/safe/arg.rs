@@ -0,0 +1,77 @@
+use std::mem;
+
+use crate::{BufferView, ImageView, ParameterDesc, ParameterFlags, TypeId, TypeInfo};
+
+pub unsafe trait RpsArg: Copy + 'static {
+    const TYPE_INFO: TypeInfo = TypeInfo {
+        size: mem::size_of::<Self>() as _,
+        id: TypeId::OPAQUE.into_raw() as _
+    };
+    const ARRAY_SIZE: u32 = 0;
+    const IS_RESOURCE: bool = false;
+
+    // Opaque parameters only carry a size; any other type id, user-defined ones included, has to match exactly.
+    #[inline]
+    fn matches_element(type_info: TypeInfo, flags: ParameterFlags) -> bool {
+        let type_id_matches = type_info.id == Self::TYPE_INFO.id || TypeId::from_raw(type_info.id as _) == TypeId::OPAQUE;
+
+        type_info.size == Self::TYPE_INFO.size && !flags.contains(ParameterFlags::RESOURCE) && type_id_matches
+    }
+
+    #[inline]
+    fn matches(param_desc: &ParameterDesc) -> bool {
+        param_desc.array_size == Self::ARRAY_SIZE && Self::matches_element(param_desc.type_info, param_desc.flags)
+    }
+}
+
+macro_rules! impl_built_in_arg {
+    ($resource: literal; $($type_: ty => [$type_id: ident $(, $alias_id: ident)*]);* $(;)?) => {
+        $(
+            unsafe impl RpsArg for $type_ {
+                const TYPE_INFO: TypeInfo = TypeInfo {
+                    size: mem::size_of::<Self>() as _,
+                    id: TypeId::$type_id.into_raw() as _
+                };
+                const IS_RESOURCE: bool = $resource;
+
+                #[inline]
+                fn matches_element(type_info: TypeInfo, flags: ParameterFlags) -> bool {
+                    type_info.size == Self::TYPE_INFO.size
+                        && flags.contains(ParameterFlags::RESOURCE) == Self::IS_RESOURCE
+                        && [TypeId::$type_id $(, TypeId::$alias_id)*].contains(&TypeId::from_raw(type_info.id as _))
+                }
+            }
+        )*
+    };
+}
+
+impl_built_in_arg! {
+    false;
+    i8 => [INT8];
+    u8 => [UINT8];
+    i16 => [INT16];
+    u16 => [UINT16];
+    i32 => [INT32, BOOL];
+    u32 => [UINT32];
+    i64 => [INT64];
+    u64 => [UINT64];
+    f32 => [FLOAT32];
+    f64 => [FLOAT64]
+}
+
+impl_built_in_arg! {
+    true;
+    ImageView => [IMAGE_VIEW];
+    BufferView => [BUFFER_VIEW]
+}
+
+unsafe impl<T: RpsArg, const N: usize> RpsArg for [T; N] {
+    const TYPE_INFO: TypeInfo = T::TYPE_INFO;
+    const ARRAY_SIZE: u32 = N as _;
+    const IS_RESOURCE: bool = T::IS_RESOURCE;
+
+    #[inline]
+    fn matches_element(type_info: TypeInfo, flags: ParameterFlags) -> bool {
+        T::matches_element(type_info, flags)
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    ffi::c_char,
+    mem,
+    ptr::{self, NonNull},
+    slice, str
+};
+
+use crate::{
+    cmd_begin_render_pass, cmd_callback_report_error, cmd_end_render_pass, cmd_get_arg_resource_access_info, cmd_get_arg_resource_access_info_array, cmd_get_arg_resource_desc,
+    cmd_get_arg_resource_desc_array, cmd_get_arg_runtime_resource, cmd_get_arg_runtime_resource_array, cmd_get_node_name, cmd_get_param_desc, cmd_get_render_targets_info,
+    cmd_get_viewport_info, core::with_c_name, safe::RpsArg, CmdCallbackContext, CmdRenderPassBeginInfo, CmdRenderTargetInfo, CmdViewportInfo, ErrorContext, ParamId, ParameterDesc,
+    ResourceAccessInfo, ResourceDesc, Result, RpsError, RpsResult, RuntimeCommandBuffer, RuntimeRenderPassFlags, RuntimeResource, INDEX_NONE_U32
+};
+
+pub struct CmdContext<'a> {
+    raw: &'a CmdCallbackContext
+}
+
+impl<'a> CmdContext<'a> {
+    #[inline]
+    pub unsafe fn from_raw(raw: *const CmdCallbackContext) -> Self {
+        Self { raw: &*raw }
+    }
+
+    #[inline]
+    pub fn raw(&self) -> &'a CmdCallbackContext {
+        self.raw
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const CmdCallbackContext {
+        self.raw
+    }
+
+    #[inline]
+    pub fn command_buffer(&self) -> RuntimeCommandBuffer {
+        self.raw.command_buffer
+    }
+
+    #[inline]
+    pub fn user_tag(&self) -> u32 {
+        self.raw.user_tag
+    }
+
+    #[inline]
+    pub fn num_args(&self) -> u32 {
+        self.raw.num_args
+    }
+
+    pub fn node_name(&self) -> RpsResult<&'a str> {
+        let mut name: *const c_char = ptr::null();
+        let mut name_length = 0;
+        unsafe { cmd_get_node_name(self.raw, &mut name, &mut name_length) }?;
+        if name.is_null() {
+            return Ok("");
+        }
+
+        let name = unsafe { slice::from_raw_parts(name.cast::<u8>(), name_length) };
+        str::from_utf8(name).map_err(|error| RpsError::InvalidData(ErrorContext::new("rpsCmdGetNodeName")).with_source(error))
+    }
+
+    #[inline]
+    pub fn param_desc(&self, index: ParamId) -> RpsResult<ParameterDesc> {
+        unsafe { cmd_get_param_desc(self.raw, index) }
+    }
+
+    fn array_len(&self, index: ParamId, api: &'static str) -> RpsResult<usize> {
+        let param_desc = self.param_desc(index)?;
+        if param_desc.array_size == INDEX_NONE_U32 {
+            return Err(unsafe { with_c_name(RpsError::NotSupported(ErrorContext::new(api)), param_desc.name) });
+        }
+
+        Ok(param_desc.array_size.max(1) as _)
+    }
+
+    pub fn arg<T: RpsArg>(&self, index: ParamId) -> RpsResult<&'a T> {
+        let param_desc = self.param_desc(index)?;
+        let arg = self.arg_ptr(index)?;
+
+        if !T::matches(&param_desc) || !arg.cast::<T>().is_aligned() {
+            return Err(unsafe { with_c_name(RpsError::TypeMismatch(ErrorContext::new("CmdContext::arg")), param_desc.name) });
+        }
+
+        Ok(unsafe { arg.cast::<T>().as_ref() })
+    }
+
+    fn arg_ptr(&self, index: ParamId) -> RpsResult<NonNull<u8>> {
+        if index >= self.raw.num_args {
+            return Err(RpsError::IndexOutOfBounds(ErrorContext::new("CmdContext::arg")));
+        }
+
+        NonNull::new(unsafe { *self.raw.args.add(index as _) }.cast::<u8>()).ok_or_else(|| RpsError::InvalidData(ErrorContext::new("CmdContext::arg")))
+    }
+
+    #[inline]
+    pub fn resource_desc(&self, index: ParamId) -> RpsResult<ResourceDesc> {
+        unsafe { cmd_get_arg_resource_desc(self.raw, index) }
+    }
+
+    #[inline]
+    pub fn resource_descs_into(&self, index: ParamId, array_offset: u32, resource_descs: &mut [ResourceDesc]) -> RpsResult<()> {
+        unsafe { cmd_get_arg_resource_desc_array(self.raw, index, array_offset, resource_descs.as_mut_ptr(), resource_descs.len() as _) }
+    }
+
+    pub fn resource_descs(&self, index: ParamId) -> RpsResult<Vec<ResourceDesc>> {
+        let mut resource_descs = vec![ResourceDesc::default(); self.array_len(index, "CmdContext::resource_descs")?];
+        self.resource_descs_into(index, 0, &mut resource_descs)?;
+        Ok(resource_descs)
+    }
+
+    #[inline]
+    pub fn resource_access_info(&self, index: ParamId) -> RpsResult<ResourceAccessInfo> {
+        unsafe { cmd_get_arg_resource_access_info(self.raw, index) }
+    }
+
+    #[inline]
+    pub fn resource_access_infos_into(&self, index: ParamId, array_offset: u32, resource_access_infos: &mut [ResourceAccessInfo]) -> RpsResult<()> {
+        unsafe { cmd_get_arg_resource_access_info_array(self.raw, index, array_offset, resource_access_infos.as_mut_ptr(), resource_access_infos.len() as _) }
+    }
+
+    pub fn resource_access_infos(&self, index: ParamId) -> RpsResult<Vec<ResourceAccessInfo>> {
+        let mut resource_access_infos = vec![ResourceAccessInfo::default(); self.array_len(index, "CmdContext::resource_access_infos")?];
+        self.resource_access_infos_into(index, 0, &mut resource_access_infos)?;
+        Ok(resource_access_infos)
+    }
+
+    #[inline]
+    pub fn runtime_resource(&self, index: ParamId) -> RpsResult<RuntimeResource> {
+        unsafe { cmd_get_arg_runtime_resource(self.raw, index) }
+    }
+
+    #[inline]
+    pub fn runtime_resources_into(&self, index: ParamId, array_offset: u32, runtime_resources: &mut [RuntimeResource]) -> RpsResult<()> {
+        unsafe { cmd_get_arg_runtime_resource_array(self.raw, index, array_offset, runtime_resources.as_mut_ptr(), runtime_resources.len() as _) }
+    }
+
+    pub fn runtime_resources(&self, index: ParamId) -> RpsResult<Vec<RuntimeResource>> {
+        let mut runtime_resources = vec![RuntimeResource::null(); self.array_len(index, "CmdContext::runtime_resources")?];
+        self.runtime_resources_into(index, 0, &mut runtime_resources)?;
+        Ok(runtime_resources)
+    }
+
+    #[inline]
+    pub fn render_targets_info(&self) -> RpsResult<CmdRenderTargetInfo> {
+        unsafe { cmd_get_render_targets_info(self.raw) }
+    }
+
+    #[inline]
+    pub fn viewport_info(&self) -> RpsResult<CmdViewportInfo> {
+        unsafe { cmd_get_viewport_info(self.raw) }
+    }
+
+    #[inline]
+    pub fn begin_render_pass(&self, flags: RuntimeRenderPassFlags) -> RpsResult<RenderPassGuard<'_, 'a>> {
+        unsafe { cmd_begin_render_pass(self.raw, &CmdRenderPassBeginInfo { flags }) }?;
+        Ok(RenderPassGuard { context: self })
+    }
+
+    #[inline]
+    pub fn report_error(&self, error_code: Result) -> RpsResult<()> {
+        unsafe { cmd_callback_report_error(self.raw, error_code) }
+    }
+}
+
+pub struct RenderPassGuard<'c, 'a> {
+    context: &'c CmdContext<'a>
+}
+
+impl RenderPassGuard<'_, '_> {
+    #[inline]
+    pub fn end(self) -> RpsResult<()> {
+        let context = self.context;
+        mem::forget(self);
+        unsafe { cmd_end_render_pass(context.raw) }
+    }
+}
+
+impl Drop for RenderPassGuard<'_, '_> {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = unsafe { cmd_end_render_pass(self.context.raw) };
+    }
+}
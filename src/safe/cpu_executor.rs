@@ -0,0 +1,357 @@
+use std::{
+    collections::HashMap,
+    mem,
+    ops::Range,
+    slice,
+    sync::{Arc, Mutex, MutexGuard, PoisonError}
+};
+
+use crate::{
+    render_graph_get_diagnostics_info,
+    safe::{CmdContext, CreateHeapArgs, CreateResourceArgs, FrameUpdate, GraphBuilder, Program, RenderGraph, RuntimeBackend},
+    ErrorContext, HeapId, ParamId, RenderGraphDiagnosticInfoFlags, ResourceDesc, ResourceId, ResourceType, RpsError, RpsResult, RuntimeHeap, RuntimeResource, INDEX_NONE_U32
+};
+
+fn host_size(desc: &ResourceDesc) -> usize {
+    if desc.type_ == ResourceType::BUFFER {
+        let buffer = unsafe { desc.buffer_image.buffer };
+        return (u64::from(buffer.size_in_bytes_hi) << 32 | u64::from(buffer.size_in_bytes_lo)) as _;
+    }
+
+    let image = unsafe { desc.buffer_image.image };
+    let block_size = if image.format.block_compressed() { 4 } else { 1 };
+    let element_bytes = image.format.element_bytes() as usize * image.sample_count.max(1) as usize;
+
+    (0..image.mip_levels.max(1))
+        .map(|mip_level| {
+            let width = (image.width >> mip_level).max(1).div_ceil(block_size) as usize;
+            let height = (image.height >> mip_level).max(1).div_ceil(block_size) as usize;
+            let depth = if desc.type_ == ResourceType::IMAGE_3D {
+                (image.depth_or_array_layers >> mip_level).max(1)
+            } else {
+                image.depth_or_array_layers.max(1)
+            } as usize;
+
+            width * height * depth * element_bytes
+        })
+        .sum()
+}
+
+#[inline]
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+#[derive(Clone, Debug)]
+enum Backing {
+    Dedicated(Vec<u8>),
+    Placed { heap: u64, range: Range<usize> }
+}
+
+#[derive(Debug)]
+struct CpuHeap {
+    memory_type_index: u32,
+    data: Vec<u8>
+}
+
+#[derive(Clone, Debug)]
+struct CpuResource {
+    render_graph: crate::RenderGraph,
+    resource_id: ResourceId,
+    backing: Backing
+}
+
+struct HostSpan {
+    ptr: *mut u8,
+    len: usize,
+    placement: Option<(u64, Range<usize>)>
+}
+
+impl HostSpan {
+    #[inline]
+    fn overlaps(&self, other: &HostSpan) -> bool {
+        match (&self.placement, &other.placement) {
+            (Some((heap, range)), Some((other_heap, other_range))) => heap == other_heap && overlaps(range, other_range),
+            _ => false
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CpuMemory {
+    heaps: HashMap<u64, CpuHeap>,
+    resources: HashMap<u64, CpuResource>,
+    resource_handles: HashMap<(crate::RenderGraph, ResourceId), u64>,
+    render_graph: crate::RenderGraph,
+    next_handle: u64
+}
+
+impl CpuMemory {
+    #[inline]
+    fn allocate_handle(&mut self) -> u64 {
+        self.next_handle += 1;
+        self.next_handle
+    }
+
+    // The create-heap callback carries no heap id, but the graph's heap diagnostics are indexed by heap id and name the runtime heap created for each.
+    fn heap_for_placement(&self, heap_id: HeapId) -> Option<u64> {
+        if self.render_graph == crate::RenderGraph::null() {
+            return None;
+        }
+
+        let info = unsafe { render_graph_get_diagnostics_info(self.render_graph, RenderGraphDiagnosticInfoFlags::DEFAULT) }.ok()?;
+        if info.heap_diag_infos.is_null() || heap_id >= info.num_heap_infos {
+            return None;
+        }
+
+        let heap = unsafe { (*info.heap_diag_infos.add(heap_id as _)).runtime_heap }.into_raw() as u64;
+        self.heaps.contains_key(&heap).then_some(heap)
+    }
+
+    fn bytes(&self, render_graph: crate::RenderGraph, resource_id: ResourceId) -> Option<&[u8]> {
+        let resource = self.resources.get(self.resource_handles.get(&(render_graph, resource_id))?)?;
+        match &resource.backing {
+            Backing::Dedicated(data) => Some(data),
+            Backing::Placed { heap, range } => self.heaps.get(heap)?.data.get(range.clone())
+        }
+    }
+
+    fn span(&mut self, resource: RuntimeResource) -> Option<HostSpan> {
+        let resource = self.resources.get_mut(&(resource.into_raw() as u64))?;
+        match &mut resource.backing {
+            Backing::Dedicated(data) => {
+                Some(HostSpan {
+                    ptr: data.as_mut_ptr(),
+                    len: data.len(),
+                    placement: None
+                })
+            }
+            Backing::Placed { heap, range } => {
+                let data = self.heaps.get_mut(heap)?.data.get_mut(range.clone())?;
+                Some(HostSpan {
+                    ptr: data.as_mut_ptr(),
+                    len: data.len(),
+                    placement: Some((*heap, range.clone()))
+                })
+            }
+        }
+    }
+}
+
+pub struct CpuNodeContext<'c, 'a> {
+    cmd: &'c mut CmdContext<'a>,
+    memory: &'c mut CpuMemory
+}
+
+impl<'a> CpuNodeContext<'_, 'a> {
+    #[inline]
+    pub fn cmd(&self) -> &CmdContext<'a> {
+        self.cmd
+    }
+
+    #[inline]
+    pub fn resource_id(&self, arg_index: ParamId) -> RpsResult<ResourceId> {
+        Ok(self.cmd.resource_access_info(arg_index)?.resource_id)
+    }
+
+    #[inline]
+    pub fn resource(&mut self, arg_index: ParamId) -> RpsResult<&mut [u8]> {
+        Ok(self.resources(&[arg_index])?.remove(0))
+    }
+
+    pub fn resources(&mut self, arg_indices: &[ParamId]) -> RpsResult<Vec<&mut [u8]>> {
+        let mut spans: Vec<(RuntimeResource, HostSpan)> = Vec::with_capacity(arg_indices.len());
+        for &arg_index in arg_indices {
+            let resource = self.cmd.runtime_resource(arg_index)?;
+            let span = self.memory.span(resource).ok_or_else(|| RpsError::InvalidData(ErrorContext::new("CpuNodeContext::resources")))?;
+
+            if spans.iter().any(|(other_resource, other)| *other_resource == resource || span.overlaps(other)) {
+                return Err(RpsError::RangeOverlapping(ErrorContext::new("CpuNodeContext::resources")));
+            }
+
+            spans.push((resource, span));
+        }
+
+        Ok(spans.into_iter().map(|(_, span)| unsafe { slice::from_raw_parts_mut(span.ptr, span.len) }).collect())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct CpuExecutor {
+    memory: Arc<Mutex<CpuMemory>>
+}
+
+impl CpuExecutor {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn memory(&self) -> MutexGuard<'_, CpuMemory> {
+        self.memory.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    pub fn backend(&self) -> CpuBackend {
+        CpuBackend { executor: self.clone() }
+    }
+
+    pub fn node<F>(&self, mut callback: F) -> impl FnMut(&mut CmdContext<'_>) -> RpsResult<()> + Send + 'static
+    where
+        F: FnMut(&mut CpuNodeContext<'_, '_>) -> RpsResult<()> + Send + 'static
+    {
+        let executor = self.clone();
+        move |cmd: &mut CmdContext<'_>| {
+            let mut memory = executor.memory();
+            callback(&mut CpuNodeContext { cmd, memory: &mut memory })
+        }
+    }
+
+    #[inline]
+    pub fn bind_node<F>(&self, program: &mut Program<'_>, name: &str, callback: F) -> RpsResult<()>
+    where
+        F: FnMut(&mut CpuNodeContext<'_, '_>) -> RpsResult<()> + Send + 'static
+    {
+        program.bind_node(name, self.node(callback))
+    }
+
+    // Placements and resource ids are resolved against the render graph being updated, so graphs need to be updated through the executor.
+    pub fn with_render_graph<R>(&self, render_graph: crate::RenderGraph, f: impl FnOnce() -> R) -> R {
+        let previous = mem::replace(&mut self.memory().render_graph, render_graph);
+        let result = f();
+        self.memory().render_graph = previous;
+        result
+    }
+
+    #[inline]
+    pub fn update(&self, render_graph: &mut RenderGraph<'_>, frame_update: &FrameUpdate) -> RpsResult<()> {
+        self.with_render_graph(render_graph.handle(), || render_graph.update(frame_update))
+    }
+
+    #[inline]
+    pub fn update_with(&self, render_graph: &mut RenderGraph<'_>, frame_update: &FrameUpdate, build: impl FnMut(&mut GraphBuilder<'_>) -> RpsResult<()>) -> RpsResult<()> {
+        self.with_render_graph(render_graph.handle(), || render_graph.update_with(frame_update, build))
+    }
+
+    #[inline]
+    pub fn resource_data(&self, render_graph: crate::RenderGraph, resource_id: ResourceId) -> Option<Vec<u8>> {
+        self.memory().bytes(render_graph, resource_id).map(<[u8]>::to_vec)
+    }
+
+    #[inline]
+    pub fn num_heaps(&self) -> usize {
+        self.memory().heaps.len()
+    }
+
+    #[inline]
+    pub fn num_resources(&self) -> usize {
+        self.memory().resources.len()
+    }
+
+    pub fn aliased_resources(&self, render_graph: crate::RenderGraph) -> Vec<(ResourceId, ResourceId)> {
+        let memory = self.memory();
+        let placed = memory
+            .resources
+            .values()
+            .filter(|resource| resource.render_graph == render_graph)
+            .filter_map(|resource| {
+                match &resource.backing {
+                    Backing::Placed { heap, range } => Some((resource.resource_id, *heap, range.clone())),
+                    Backing::Dedicated(_) => None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut aliased = placed
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (resource_id, heap, range))| {
+                placed[index + 1..]
+                    .iter()
+                    .filter(move |(other_id, other_heap, other_range)| other_id != resource_id && other_heap == heap && overlaps(range, other_range))
+                    .map(move |(other_id, _, _)| ((*resource_id).min(*other_id), (*resource_id).max(*other_id)))
+            })
+            .collect::<Vec<_>>();
+        aliased.sort_unstable();
+        aliased.dedup();
+        aliased
+    }
+}
+
+pub struct CpuBackend {
+    executor: CpuExecutor
+}
+
+impl RuntimeBackend for CpuBackend {
+    fn create_heap(&mut self, args: &CreateHeapArgs<'_>) -> RpsResult<RuntimeHeap> {
+        let mut memory = self.executor.memory();
+        let handle = memory.allocate_handle();
+        let heap = CpuHeap {
+            memory_type_index: args.memory_type_index,
+            data: vec![0; args.size]
+        };
+
+        memory.heaps.insert(handle, heap);
+        Ok(RuntimeHeap::from_raw(handle as _))
+    }
+
+    fn destroy_heaps(&mut self, heaps: &[RuntimeHeap]) {
+        let mut memory = self.executor.memory();
+        for heap in heaps {
+            let handle = heap.into_raw() as u64;
+            memory.heaps.remove(&handle);
+        }
+    }
+
+    fn create_resource(&mut self, args: &CreateResourceArgs<'_>) -> RpsResult<RuntimeResource> {
+        let host_size = host_size(args.desc);
+        let heap_id = args.alloc_placement.heap_id;
+
+        let mut memory = self.executor.memory();
+        let backing = if heap_id == INDEX_NONE_U32 {
+            Backing::Dedicated(vec![0; (args.alloc_requirement.size as usize).max(host_size)])
+        } else {
+            let placement_error = || RpsError::IndexOutOfBounds(ErrorContext::new("CpuBackend::create_resource"));
+            if host_size > args.alloc_requirement.size as usize {
+                return Err(placement_error());
+            }
+
+            let start = usize::try_from(args.alloc_placement.offset).map_err(|_| placement_error())?;
+            let end = start.checked_add(args.alloc_requirement.size as usize).ok_or_else(placement_error)?;
+            let heap = memory.heap_for_placement(heap_id).ok_or_else(placement_error)?;
+            let cpu_heap = &memory.heaps[&heap];
+            if cpu_heap.memory_type_index != args.alloc_requirement.memory_type_index || cpu_heap.data.len() < end {
+                return Err(placement_error());
+            }
+
+            Backing::Placed { heap, range: start..end }
+        };
+
+        let handle = memory.allocate_handle();
+        let render_graph = memory.render_graph;
+        memory.resources.insert(
+            handle,
+            CpuResource {
+                render_graph,
+                resource_id: args.resource_id,
+                backing
+            }
+        );
+        memory.resource_handles.insert((render_graph, args.resource_id), handle);
+        Ok(RuntimeResource::from_raw(handle as _))
+    }
+
+    fn destroy_resources(&mut self, _resource_type: ResourceType, resources: &[RuntimeResource]) {
+        let mut memory = self.executor.memory();
+        for resource in resources {
+            let handle = resource.into_raw() as u64;
+            if let Some(resource) = memory.resources.remove(&handle) {
+                let key = (resource.render_graph, resource.resource_id);
+                if memory.resource_handles.get(&key) == Some(&handle) {
+                    memory.resource_handles.remove(&key);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::{any, ffi::c_void, mem, ptr, ptr::NonNull};
+
+use crate::{
+    device_create, device_destroy, device_get_private_data,
+    safe::{AllocationStats, HostAllocator, RuntimeBackend, RuntimeBackendHost},
+    AllocInfo, Allocator, DeviceCreateInfo, ErrorContext, PfnDeviceOnDestroy, Printer, RpsError, RpsResult
+};
+
+struct PrivateData {
+    type_id: any::TypeId,
+    ptr: NonNull<c_void>,
+    drop: unsafe fn(*mut c_void)
+}
+
+unsafe fn drop_private_data<T>(ptr: *mut c_void) {
+    ptr::drop_in_place(ptr.cast::<T>());
+}
+
+pub struct Device {
+    handle: crate::Device,
+    private_data: Option<PrivateData>,
+    host_allocator: Option<HostAllocator>
+}
+
+unsafe impl Send for Device {}
+unsafe impl Sync for Device {}
+
+impl Device {
+    #[inline]
+    pub fn builder() -> DeviceBuilder<()> {
+        DeviceBuilder::default()
+    }
+
+    #[inline]
+    pub fn handle(&self) -> crate::Device {
+        self.handle
+    }
+
+    #[inline]
+    pub fn private_data<T: 'static>(&self) -> Option<&T> {
+        self.private_data
+            .as_ref()
+            .filter(|private_data| private_data.type_id == any::TypeId::of::<T>())
+            .map(|private_data| unsafe { private_data.ptr.cast::<T>().as_ref() })
+    }
+
+    #[inline]
+    pub fn allocation_stats(&self) -> Option<AllocationStats> {
+        self.host_allocator.as_ref().map(HostAllocator::stats)
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(private_data) = self.private_data.take() {
+                (private_data.drop)(private_data.ptr.as_ptr());
+            }
+
+            device_destroy(self.handle);
+        }
+    }
+}
+
+pub struct DeviceBuilder<T> {
+    allocator: Allocator,
+    host_allocator: Option<HostAllocator>,
+    printer: Printer,
+    pfn_device_on_destroy: PfnDeviceOnDestroy,
+    private_data: Option<T>
+}
+
+impl Default for DeviceBuilder<()> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            allocator: Allocator::default(),
+            host_allocator: None,
+            printer: Printer::default(),
+            pfn_device_on_destroy: None,
+            private_data: None
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> DeviceBuilder<T> {
+    #[inline]
+    pub unsafe fn allocator(mut self, allocator: Allocator) -> Self {
+        self.allocator = allocator;
+        self.host_allocator = None;
+        self
+    }
+
+    #[inline]
+    pub fn host_allocator(mut self, host_allocator: HostAllocator) -> Self {
+        self.allocator = host_allocator.allocator();
+        self.host_allocator = Some(host_allocator);
+        self
+    }
+
+    #[inline]
+    pub unsafe fn printer(mut self, printer: Printer) -> Self {
+        self.printer = printer;
+        self
+    }
+
+    #[cfg(feature = "log")]
+    #[inline]
+    pub fn log_printer(mut self, level: crate::DiagLogLevel) -> Self {
+        self.printer = crate::safe::log_printer(level);
+        self
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn tracing_printer(mut self, level: crate::DiagLogLevel) -> Self {
+        self.printer = crate::safe::tracing_printer(level);
+        self
+    }
+
+    #[inline]
+    pub unsafe fn on_destroy(mut self, pfn_device_on_destroy: PfnDeviceOnDestroy) -> Self {
+        self.pfn_device_on_destroy = pfn_device_on_destroy;
+        self
+    }
+
+    #[inline]
+    pub fn private_data<U: Send + Sync + 'static>(self, private_data: U) -> DeviceBuilder<U> {
+        DeviceBuilder {
+            allocator: self.allocator,
+            host_allocator: self.host_allocator,
+            printer: self.printer,
+            pfn_device_on_destroy: self.pfn_device_on_destroy,
+            private_data: Some(private_data)
+        }
+    }
+
+    #[inline]
+    pub fn build(self) -> RpsResult<Device> {
+        unsafe { self.build_with(|create_info| device_create(create_info)) }
+    }
+
+    pub fn build_with_backend(self, backend: impl RuntimeBackend) -> RpsResult<Device> {
+        let host = RuntimeBackendHost::new(Box::new(backend));
+        unsafe { self.build_with(|device_create_info| host.create_device(device_create_info)) }
+    }
+
+    pub unsafe fn build_with(self, create: impl FnOnce(&DeviceCreateInfo) -> RpsResult<crate::Device>) -> RpsResult<Device> {
+        let private_data_alloc_info = if self.private_data.is_some() {
+            AllocInfo {
+                size: mem::size_of::<T>(),
+                alignment: mem::align_of::<T>()
+            }
+        } else {
+            AllocInfo::default()
+        };
+
+        let create_info = DeviceCreateInfo {
+            allocator: self.allocator,
+            printer: self.printer,
+            private_data_alloc_info,
+            pfn_device_on_destroy: self.pfn_device_on_destroy
+        };
+
+        let handle = create(&create_info)?;
+
+        let private_data = match self.private_data {
+            Some(value) => {
+                let ptr = if mem::size_of::<T>() == 0 {
+                    NonNull::<T>::dangling().cast()
+                } else if let Some(ptr) = NonNull::new(device_get_private_data(handle).cast_mut()) {
+                    ptr
+                } else {
+                    device_destroy(handle);
+                    return Err(RpsError::OutOfMemory(ErrorContext::new("rpsDeviceGetPrivateData")));
+                };
+
+                ptr.cast::<T>().as_ptr().write(value);
+
+                Some(PrivateData {
+                    type_id: any::TypeId::of::<T>(),
+                    ptr,
+                    drop: drop_private_data::<T>
+                })
+            }
+            None => None
+        };
+
+        Ok(Device {
+            handle,
+            private_data,
+            host_allocator: self.host_allocator
+        })
+    }
+}
@@ -0,0 +1,396 @@
+use std::{
+    collections::HashMap,
+    ffi::{c_char, CStr},
+    fmt::Write,
+    marker::PhantomData,
+    mem, slice
+};
+
+use crate::{
+    safe::RenderGraph, AccessAttr, ClearValue, CmdDiagnosticInfo, CommandBatch, GpuMemoryRequirement, HeapDiagnosticInfo, HeapPlacement, NodeId, RenderGraphBatchLayout,
+    RenderGraphDiagnosticInfo, ResourceDesc, ResourceDiagnosticInfo, RuntimeResource, SubresourceRange
+};
+
+#[inline]
+unsafe fn raw_slice<'a, T>(data: *const T, len: u32) -> &'a [T] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len as _)
+    }
+}
+
+#[inline]
+unsafe fn c_str_to_string(name: *const c_char) -> String {
+    if name.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    }
+}
+
+#[derive(Clone)]
+pub struct ResourceDiagnostic {
+    pub name: String,
+    pub temporal_child_index: u32,
+    pub is_external: bool,
+    pub desc: ResourceDesc,
+    pub clear_value: ClearValue,
+    pub all_accesses: AccessAttr,
+    pub initial_access: AccessAttr,
+    pub lifetime_begin: u32,
+    pub lifetime_end: u32,
+    pub alloc_requirement: GpuMemoryRequirement,
+    pub alloc_placement: HeapPlacement,
+    pub runtime_resource: RuntimeResource
+}
+
+impl ResourceDiagnostic {
+    #[inline]
+    pub unsafe fn from_raw(info: &ResourceDiagnosticInfo) -> Self {
+        Self {
+            name: c_str_to_string(info.name),
+            temporal_child_index: info.temporal_child_index,
+            is_external: info.is_external != 0,
+            desc: info.desc,
+            clear_value: info.clear_value,
+            all_accesses: info.all_accesses,
+            initial_access: info.initial_access,
+            lifetime_begin: info.lifetime_begin,
+            lifetime_end: info.lifetime_end,
+            alloc_requirement: info.alloc_requirement,
+            alloc_placement: info.alloc_placement,
+            runtime_resource: info.runtime_resource
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum CmdDiagnostic {
+    Node {
+        cmd_index: u32
+    },
+    Transition {
+        cmd_index: u32,
+        prev_access: AccessAttr,
+        next_access: AccessAttr,
+        range: SubresourceRange,
+        resource_index: u32
+    }
+}
+
+impl CmdDiagnostic {
+    #[inline]
+    pub fn from_raw(info: &CmdDiagnosticInfo) -> Self {
+        if info.is_transition != 0 {
+            let transition = unsafe { info.transition.transition };
+            Self::Transition {
+                cmd_index: info.cmd_index,
+                prev_access: transition.prev_access,
+                next_access: transition.next_access,
+                range: transition.range,
+                resource_index: transition.resource_index
+            }
+        } else {
+            Self::Node { cmd_index: info.cmd_index }
+        }
+    }
+
+    #[inline]
+    pub fn cmd_index(&self) -> u32 {
+        match *self {
+            Self::Node { cmd_index } | Self::Transition { cmd_index, .. } => cmd_index
+        }
+    }
+
+    #[inline]
+    pub fn is_transition(&self) -> bool {
+        matches!(self, Self::Transition { .. })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NodeResourceAccess {
+    pub resource_index: u32,
+    pub access: AccessAttr
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NodeDiagnostic {
+    pub name: String,
+    pub accesses: Vec<NodeResourceAccess>
+}
+
+pub type NodeDiagnostics = HashMap<NodeId, NodeDiagnostic>;
+
+#[derive(Clone, Copy)]
+pub struct Diagnostics<'graph> {
+    resources: &'graph [ResourceDiagnosticInfo],
+    commands: &'graph [CmdDiagnosticInfo],
+    heaps: &'graph [HeapDiagnosticInfo],
+    batches: &'graph [CommandBatch],
+    wait_fence_indices: &'graph [u32],
+    nodes: &'graph NodeDiagnostics,
+    _render_graph: PhantomData<&'graph RenderGraph<'graph>>
+}
+
+impl<'graph> Diagnostics<'graph> {
+    // Without a batch layout (e.g. the graph was never scheduled) every command is reported as unscheduled.
+    #[inline]
+    pub(crate) unsafe fn from_raw(info: &RenderGraphDiagnosticInfo, batch_layout: Option<&RenderGraphBatchLayout>, nodes: &'graph NodeDiagnostics) -> Self {
+        let (batches, wait_fence_indices) = match batch_layout {
+            Some(batch_layout) => {
+                let batches = raw_slice(batch_layout.cmd_batches, batch_layout.num_cmd_batches);
+                let num_wait_fence_indices = batches.iter().map(|batch| batch.wait_fences_begin + batch.num_wait_fences).max().unwrap_or_default();
+                (batches, raw_slice(batch_layout.wait_fence_indices, num_wait_fence_indices))
+            }
+            None => (&[][..], &[][..])
+        };
+
+        Self {
+            resources: raw_slice(info.resource_diag_infos, info.num_resource_infos),
+            commands: raw_slice(info.cmd_diag_infos, info.num_command_infos),
+            heaps: raw_slice(info.heap_diag_infos, info.num_heap_infos),
+            batches,
+            wait_fence_indices,
+            nodes,
+            _render_graph: PhantomData
+        }
+    }
+
+    #[inline]
+    pub fn resource_infos(&self) -> &'graph [ResourceDiagnosticInfo] {
+        self.resources
+    }
+
+    #[inline]
+    pub fn cmd_infos(&self) -> &'graph [CmdDiagnosticInfo] {
+        self.commands
+    }
+
+    #[inline]
+    pub fn heap_infos(&self) -> &'graph [HeapDiagnosticInfo] {
+        self.heaps
+    }
+
+    #[inline]
+    pub fn batches(&self) -> &'graph [CommandBatch] {
+        self.batches
+    }
+
+    #[inline]
+    pub fn node(&self, cmd_index: u32) -> Option<&'graph NodeDiagnostic> {
+        self.nodes.get(&cmd_index)
+    }
+
+    #[inline]
+    pub fn resource(&self, index: usize) -> Option<ResourceDiagnostic> {
+        self.resources.get(index).map(|info| unsafe { ResourceDiagnostic::from_raw(info) })
+    }
+
+    #[inline]
+    pub fn resources(&self) -> impl ExactSizeIterator<Item = ResourceDiagnostic> + 'graph {
+        self.resources.iter().map(|info| unsafe { ResourceDiagnostic::from_raw(info) })
+    }
+
+    #[inline]
+    pub fn commands(&self) -> impl ExactSizeIterator<Item = CmdDiagnostic> + 'graph {
+        self.commands.iter().map(CmdDiagnostic::from_raw)
+    }
+
+    #[inline]
+    pub fn snapshot(&self) -> DiagnosticsSnapshot {
+        DiagnosticsSnapshot {
+            resources: self.resources().collect(),
+            commands: self.commands().collect(),
+            heaps: self.heaps.to_vec(),
+            batches: self.batches.to_vec(),
+            wait_fence_indices: self.wait_fence_indices.to_vec(),
+            nodes: self.nodes.clone()
+        }
+    }
+
+    #[inline]
+    pub fn to_dot(&self) -> String {
+        self.snapshot().to_dot()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct DiagnosticsSnapshot {
+    pub resources: Vec<ResourceDiagnostic>,
+    pub commands: Vec<CmdDiagnostic>,
+    pub heaps: Vec<HeapDiagnosticInfo>,
+    pub batches: Vec<CommandBatch>,
+    pub wait_fence_indices: Vec<u32>,
+    pub nodes: NodeDiagnostics
+}
+
+const QUEUE_COLORS: &[&str] = &["lightblue", "lightsalmon", "palegreen", "plum", "khaki", "lightgray"];
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn access_label(access: &AccessAttr) -> String {
+    if access.access_flags.is_empty() {
+        return "UNKNOWN".to_owned();
+    }
+
+    access.access_flags.iter_names().map(|(name, _)| name).collect::<Vec<_>>().join(" | ")
+}
+
+fn combined_access(accesses: &[NodeResourceAccess]) -> AccessAttr {
+    accesses.iter().fold(AccessAttr::default(), |combined, access| {
+        AccessAttr {
+            access_flags: combined.access_flags | access.access.access_flags,
+            access_stages: combined.access_stages | access.access.access_stages
+        }
+    })
+}
+
+impl DiagnosticsSnapshot {
+    fn queue_index(&self, cmd_position: usize) -> Option<u32> {
+        self.batches
+            .iter()
+            .find(|batch| (batch.cmd_begin as usize..(batch.cmd_begin + batch.num_cmds) as usize).contains(&cmd_position))
+            .map(|batch| batch.queue_index)
+    }
+
+    fn resource_name(&self, resource_index: u32) -> String {
+        match self.resources.get(resource_index as usize) {
+            Some(resource) if !resource.name.is_empty() => resource.name.clone(),
+            _ => format!("resource {resource_index}")
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let _ = self.write_dot(&mut dot);
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String) -> std::fmt::Result {
+        writeln!(dot, "digraph render_graph {{")?;
+        writeln!(dot, "    rankdir=LR;")?;
+        writeln!(dot, "    node [style=filled];")?;
+
+        let mut queues = self.batches.iter().map(|batch| batch.queue_index).collect::<Vec<_>>();
+        queues.sort_unstable();
+        queues.dedup();
+
+        let mut queue_groups = queues.iter().map(|&queue_index| (Some(queue_index), Vec::new())).collect::<Vec<_>>();
+        queue_groups.push((None, Vec::new()));
+        for (position, command) in self.commands.iter().enumerate() {
+            let queue_index = self.queue_index(position);
+            if let Some((_, commands)) = queue_groups.iter_mut().find(|(group_queue, _)| *group_queue == queue_index) {
+                commands.push((position, command));
+            }
+        }
+
+        let mut node_edges = Vec::new();
+        for (queue_index, commands) in &queue_groups {
+            if commands.is_empty() {
+                continue;
+            }
+
+            let color = queue_index.map_or("white", |queue_index| QUEUE_COLORS[queue_index as usize % QUEUE_COLORS.len()]);
+            if let Some(queue_index) = queue_index {
+                writeln!(dot, "    subgraph cluster_queue_{queue_index} {{")?;
+                writeln!(dot, "        label=\"queue {queue_index}\";")?;
+            } else {
+                writeln!(dot, "    subgraph cluster_unscheduled {{")?;
+                writeln!(dot, "        label=\"unscheduled\";")?;
+            }
+
+            // Nodes recorded by GraphBuilder carry their declared accesses; other nodes (e.g. from RPSL) are assumed to
+            // access the resources transitioned right before them on the same queue.
+            let mut pending_accesses = Vec::new();
+            for (position, command) in commands {
+                match command {
+                    CmdDiagnostic::Node { cmd_index } => {
+                        let node = self.nodes.get(cmd_index);
+                        let accesses = node.map_or_else(|| mem::take(&mut pending_accesses), |node| node.accesses.clone());
+                        pending_accesses.clear();
+
+                        let name = node.map_or_else(|| format!("cmd {cmd_index}"), |node| node.name.clone());
+                        let label = if accesses.is_empty() {
+                            escape_dot(&name)
+                        } else {
+                            format!("{}\\n{}", escape_dot(&name), access_label(&combined_access(&accesses)))
+                        };
+                        writeln!(dot, "        cmd_{position} [shape=box, fillcolor={color}, label=\"{label}\"];")?;
+                        node_edges.extend(accesses.into_iter().map(|access| (*position, access)));
+                    }
+                    CmdDiagnostic::Transition {
+                        prev_access,
+                        next_access,
+                        resource_index,
+                        ..
+                    } => {
+                        pending_accesses.push(NodeResourceAccess {
+                            resource_index: *resource_index,
+                            access: *next_access
+                        });
+                        writeln!(
+                            dot,
+                            "        cmd_{position} [shape=ellipse, fillcolor={color}, label=\"{}\\n{} -> {}\"];",
+                            escape_dot(&self.resource_name(*resource_index)),
+                            access_label(prev_access),
+                            access_label(next_access)
+                        )?;
+                    }
+                }
+            }
+
+            for pair in commands.windows(2) {
+                writeln!(dot, "        cmd_{} -> cmd_{};", pair[0].0, pair[1].0)?;
+            }
+
+            writeln!(dot, "    }}")?;
+        }
+
+        for (resource_index, resource) in self.resources.iter().enumerate() {
+            let shape = if resource.is_external { "doubleoctagon" } else { "note" };
+            writeln!(
+                dot,
+                "    res_{resource_index} [shape={shape}, fillcolor=white, label=\"{}\"];",
+                escape_dot(&self.resource_name(resource_index as _))
+            )?;
+        }
+
+        for (position, command) in self.commands.iter().enumerate() {
+            if let CmdDiagnostic::Transition { next_access, resource_index, .. } = command {
+                writeln!(dot, "    res_{resource_index} -> cmd_{position} [style=dotted, label=\"{}\"];", access_label(next_access))?;
+            }
+        }
+
+        for (position, access) in &node_edges {
+            writeln!(dot, "    cmd_{position} -> res_{} [label=\"{}\"];", access.resource_index, access_label(&access.access))?;
+        }
+
+        for batch in &self.batches {
+            if batch.num_cmds == 0 {
+                continue;
+            }
+
+            let wait_fences = self
+                .wait_fence_indices
+                .get(batch.wait_fences_begin as usize..(batch.wait_fences_begin + batch.num_wait_fences) as usize)
+                .unwrap_or_default();
+            for &fence_index in wait_fences {
+                let signal = self.batches.iter().find(|signal| signal.signal_fence_index == fence_index && signal.num_cmds > 0);
+                if let Some(signal) = signal {
+                    writeln!(
+                        dot,
+                        "    cmd_{} -> cmd_{} [style=dashed, color=red, label=\"fence {fence_index}\"];",
+                        signal.cmd_begin + signal.num_cmds - 1,
+                        batch.cmd_begin
+                    )?;
+                }
+            }
+        }
+
+        writeln!(dot, "}}")
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant}
+};
+
+use crate::{safe::FrameUpdate, ErrorContext, RpsError, RpsResult, GPU_COMPLETED_FRAME_INDEX_NONE, MAX_QUEUED_FRAMES};
+
+#[derive(Clone, Copy, Debug)]
+struct FrameState {
+    next_frame_index: u64,
+    gpu_completed_frame_index: u64
+}
+
+impl FrameState {
+    #[inline]
+    fn num_queued_frames(&self) -> u64 {
+        match self.gpu_completed_frame_index {
+            GPU_COMPLETED_FRAME_INDEX_NONE => self.next_frame_index,
+            gpu_completed_frame_index => self.next_frame_index - gpu_completed_frame_index - 1
+        }
+    }
+}
+
+pub struct FrameTracker {
+    max_queued_frames: u64,
+    state: Mutex<FrameState>,
+    completed: Condvar
+}
+
+impl FrameTracker {
+    #[inline]
+    pub fn new(max_queued_frames: usize) -> Self {
+        Self {
+            max_queued_frames: max_queued_frames.clamp(1, MAX_QUEUED_FRAMES) as _,
+            state: Mutex::new(FrameState {
+                next_frame_index: 0,
+                gpu_completed_frame_index: GPU_COMPLETED_FRAME_INDEX_NONE
+            }),
+            completed: Condvar::new()
+        }
+    }
+
+    #[inline]
+    fn state(&self) -> MutexGuard<'_, FrameState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    pub fn max_queued_frames(&self) -> usize {
+        self.max_queued_frames as _
+    }
+
+    #[inline]
+    pub fn frame_index(&self) -> u64 {
+        self.state().next_frame_index
+    }
+
+    #[inline]
+    pub fn gpu_completed_frame_index(&self) -> u64 {
+        self.state().gpu_completed_frame_index
+    }
+
+    #[inline]
+    pub fn num_queued_frames(&self) -> usize {
+        self.state().num_queued_frames() as _
+    }
+
+    fn advance<'u>(&self, state: &mut FrameState) -> FrameUpdate<'u> {
+        let frame_update = FrameUpdate::new(state.next_frame_index).gpu_completed_frame_index(state.gpu_completed_frame_index);
+        state.next_frame_index += 1;
+        frame_update
+    }
+
+    pub fn begin_frame<'u>(&self) -> RpsResult<FrameUpdate<'u>> {
+        let mut state = self.state();
+        if state.num_queued_frames() >= self.max_queued_frames {
+            return Err(RpsError::InvalidOperation(ErrorContext::new("FrameTracker::begin_frame")));
+        }
+
+        Ok(self.advance(&mut state))
+    }
+
+    pub fn begin_frame_blocking<'u>(&self) -> FrameUpdate<'u> {
+        let mut state = self
+            .completed
+            .wait_while(self.state(), |state| state.num_queued_frames() >= self.max_queued_frames)
+            .unwrap_or_else(PoisonError::into_inner);
+        self.advance(&mut state)
+    }
+
+    pub fn begin_frame_timeout<'u>(&self, timeout: Duration) -> RpsResult<FrameUpdate<'u>> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state();
+        while state.num_queued_frames() >= self.max_queued_frames {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(RpsError::InvalidOperation(ErrorContext::new("FrameTracker::begin_frame_timeout")));
+            }
+
+            state = self.completed.wait_timeout(state, remaining).unwrap_or_else(PoisonError::into_inner).0;
+        }
+
+        Ok(self.advance(&mut state))
+    }
+
+    pub fn complete_frame(&self, frame_index: u64) -> RpsResult<()> {
+        let mut state = self.state();
+        if frame_index >= state.next_frame_index {
+            return Err(RpsError::IndexOutOfBounds(ErrorContext::new("FrameTracker::complete_frame")));
+        }
+
+        if state.gpu_completed_frame_index == GPU_COMPLETED_FRAME_INDEX_NONE || frame_index > state.gpu_completed_frame_index {
+            state.gpu_completed_frame_index = frame_index;
+            self.completed.notify_all();
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn complete_timeline_value(&self, timeline_value: u64, first_timeline_value: u64) -> RpsResult<()> {
+        match timeline_value.checked_sub(first_timeline_value) {
+            Some(frame_index) => self.complete_frame(frame_index),
+            None => Ok(())
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.state() = FrameState {
+            next_frame_index: 0,
+            gpu_completed_frame_index: GPU_COMPLETED_FRAME_INDEX_NONE
+        };
+        self.completed.notify_all();
+    }
+}
+
+impl Default for FrameTracker {
+    #[inline]
+    fn default() -> Self {
+        Self::new(MAX_QUEUED_FRAMES)
+    }
+}
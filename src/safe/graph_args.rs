@@ -0,0 +1,139 @@
+use std::{any::Any, ptr};
+
+use crate::{
+    safe::{EntrySignature, ParamInfo, RpsArg},
+    Constant, ErrorContext, ResourceDesc, RpsError, RpsResult, RpslEntry, RuntimeResource, INDEX_NONE_U32
+};
+
+enum GraphArg {
+    Value(Box<dyn Any>),
+    Resource { descs: Vec<ResourceDesc>, resources: Vec<RuntimeResource> }
+}
+
+impl GraphArg {
+    #[inline]
+    fn arg(&self) -> Constant {
+        match self {
+            Self::Value(value) => (&**value as *const dyn Any).cast(),
+            Self::Resource { descs, .. } => descs.as_ptr().cast()
+        }
+    }
+
+    #[inline]
+    fn arg_resources(&self) -> *const RuntimeResource {
+        match self {
+            Self::Value(_) => ptr::null(),
+            Self::Resource { resources, .. } => resources.as_ptr()
+        }
+    }
+}
+
+pub struct GraphArgs {
+    params: Vec<ParamInfo>,
+    args: Vec<Option<GraphArg>>
+}
+
+impl GraphArgs {
+    #[inline]
+    pub fn new(signature: &EntrySignature) -> Self {
+        Self {
+            params: signature.params.clone(),
+            args: signature.params.iter().map(|_| None).collect()
+        }
+    }
+
+    #[inline]
+    pub fn for_entry(rpsl_entry: RpslEntry) -> RpsResult<Self> {
+        Ok(Self::new(&EntrySignature::new(rpsl_entry)?))
+    }
+
+    #[inline]
+    pub fn params(&self) -> &[ParamInfo] {
+        &self.params
+    }
+
+    #[inline]
+    pub fn param_index(&self, name: &str) -> Option<usize> {
+        self.params.iter().position(|param| param.name == name)
+    }
+
+    fn param(&self, index: usize, api: &'static str) -> RpsResult<&ParamInfo> {
+        self.params.get(index).ok_or_else(|| RpsError::IndexOutOfBounds(ErrorContext::new(api)))
+    }
+
+    fn named_param_index(&self, name: &str, api: &'static str) -> RpsResult<usize> {
+        self.param_index(name).ok_or_else(|| RpsError::KeyNotFound(ErrorContext::new(api)).with_name(name))
+    }
+
+    pub fn set<T: RpsArg>(&mut self, index: usize, value: T) -> RpsResult<&mut Self> {
+        let param = self.param(index, "GraphArgs::set")?;
+        if !param.accepts::<T>() {
+            return Err(RpsError::TypeMismatch(ErrorContext::new("GraphArgs::set")).with_name(param.name.as_str()));
+        }
+
+        self.args[index] = Some(GraphArg::Value(Box::new(value)));
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn set_named<T: RpsArg>(&mut self, name: &str, value: T) -> RpsResult<&mut Self> {
+        self.set(self.named_param_index(name, "GraphArgs::set")?, value)
+    }
+
+    #[inline]
+    pub fn set_resource(&mut self, index: usize, desc: ResourceDesc, resources: &[RuntimeResource]) -> RpsResult<&mut Self> {
+        self.set_resource_array(index, &[desc], resources)
+    }
+
+    #[inline]
+    pub fn set_named_resource(&mut self, name: &str, desc: ResourceDesc, resources: &[RuntimeResource]) -> RpsResult<&mut Self> {
+        self.set_resource(self.named_param_index(name, "GraphArgs::set_resource")?, desc, resources)
+    }
+
+    pub fn set_resource_array(&mut self, index: usize, descs: &[ResourceDesc], resources: &[RuntimeResource]) -> RpsResult<&mut Self> {
+        let param = self.param(index, "GraphArgs::set_resource")?;
+        if !param.is_resource() {
+            return Err(RpsError::TypeMismatch(ErrorContext::new("GraphArgs::set_resource")).with_name(param.name.as_str()));
+        }
+
+        if param.array_size != INDEX_NONE_U32 && descs.len() != param.array_size.max(1) as usize {
+            return Err(RpsError::InvalidArguments(ErrorContext::new("GraphArgs::set_resource")).with_name(param.name.as_str()));
+        }
+
+        let num_resources = descs.iter().map(|desc| desc.temporal_layers.max(1) as usize).sum::<usize>();
+        if resources.len() != num_resources {
+            return Err(RpsError::InvalidArguments(ErrorContext::new("GraphArgs::set_resource")).with_name(param.name.as_str()));
+        }
+
+        self.args[index] = Some(GraphArg::Resource {
+            descs: descs.to_vec(),
+            resources: resources.to_vec()
+        });
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn clear(&mut self, index: usize) {
+        if let Some(arg) = self.args.get_mut(index) {
+            *arg = None;
+        }
+    }
+
+    #[inline]
+    pub fn is_set(&self, index: usize) -> bool {
+        self.args.get(index).is_some_and(Option::is_some)
+    }
+
+    pub fn validate(&self) -> RpsResult<()> {
+        match self.params.iter().zip(&self.args).find(|(param, arg)| arg.is_none() && !param.is_optional()) {
+            Some((param, _)) => Err(RpsError::InvalidArguments(ErrorContext::new("GraphArgs::validate")).with_name(param.name.as_str())),
+            None => Ok(())
+        }
+    }
+
+    pub(crate) fn raw_args(&self) -> impl Iterator<Item = (Constant, *const RuntimeResource)> + '_ {
+        self.args
+            .iter()
+            .map(|arg| arg.as_ref().map_or((ptr::null(), ptr::null()), |arg| (arg.arg(), arg.arg_resources())))
+    }
+}
@@ -0,0 +1,514 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::HashMap,
+    ffi::{c_void, CStr},
+    marker::PhantomData,
+    mem,
+    panic::{self, AssertUnwindSafe},
+    ptr::{self, NonNull},
+    slice, str,
+    sync::Mutex
+};
+
+use crate::{
+    render_graph_add_node, render_graph_allocate_data_aligned, render_graph_allocate_data_for_type_with_len, render_graph_declare_dynamic_node, render_graph_declare_resource,
+    render_graph_update,
+    safe::{node_callback, CmdContext, NodeDiagnostic, NodeDiagnostics, NodeResourceAccess, RpsArg, StaticNodeDesc},
+    AccessAttr, BufferView, CmdCallbackFlags, Constant, ErrorContext, ImageView, NodeDeclFlags, NodeDeclId, NodeDesc, NodeId, ParamAttr, ParameterDesc, ParameterFlags, ResourceDesc,
+    ResourceId, ResourceView, Result, RpsError, RpsResult, Semantic, SemanticAttr, SubresourceRange, TypeInfo, Variable, INDEX_NONE_U32, NODEDECL_ID_INVALID, RESOURCE_ID_INVALID
+};
+
+pub(crate) type NodeCallbacks = Vec<Box<dyn Any + Send + Sync>>;
+
+#[derive(Clone, Copy, Debug)]
+pub struct NodeParam<'n> {
+    name: &'n str,
+    type_info: TypeInfo,
+    array_size: u32,
+    flags: ParameterFlags,
+    attr: ParamAttr
+}
+
+impl<'n> NodeParam<'n> {
+    #[inline]
+    pub fn image(name: &'n str, access: AccessAttr) -> Self {
+        Self::resource::<ImageView>(name, access)
+    }
+
+    #[inline]
+    pub fn buffer(name: &'n str, access: AccessAttr) -> Self {
+        Self::resource::<BufferView>(name, access)
+    }
+
+    #[inline]
+    fn resource<T: RpsArg>(name: &'n str, access: AccessAttr) -> Self {
+        Self {
+            name,
+            type_info: T::TYPE_INFO,
+            array_size: T::ARRAY_SIZE,
+            flags: ParameterFlags::RESOURCE,
+            attr: ParamAttr {
+                access,
+                semantic: SemanticAttr::default()
+            }
+        }
+    }
+
+    #[inline]
+    pub fn value<T: RpsArg>(name: &'n str) -> Self {
+        Self {
+            name,
+            type_info: T::TYPE_INFO,
+            array_size: T::ARRAY_SIZE,
+            flags: ParameterFlags::NONE,
+            attr: ParamAttr::default()
+        }
+    }
+
+    #[inline]
+    pub fn semantic(mut self, semantic: Semantic, semantic_index: u32) -> Self {
+        self.attr.semantic = SemanticAttr { semantic, semantic_index };
+        self
+    }
+
+    #[inline]
+    pub fn output(mut self) -> Self {
+        self.flags |= ParameterFlags::OUT;
+        self
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResourceHandle(ResourceId);
+
+impl ResourceHandle {
+    #[inline]
+    pub fn id(self) -> ResourceId {
+        self.0
+    }
+
+    #[inline]
+    pub fn image_view(self) -> ImageView {
+        ImageView {
+            base: ResourceView {
+                resource_id: self.0,
+                ..Default::default()
+            },
+            subresource_range: SubresourceRange {
+                base_mip_level: 0,
+                mip_levels: 1,
+                base_array_layer: 0,
+                array_layers: 1
+            },
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn buffer_view(self) -> BufferView {
+        BufferView {
+            base: ResourceView {
+                resource_id: self.0,
+                ..Default::default()
+            },
+            size_in_bytes: u64::MAX,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NodeArgData {
+    Image(ImageView),
+    Buffer(BufferView),
+    Value { data: *const u8, size: usize, alignment: usize }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NodeArg<'v> {
+    data: NodeArgData,
+    type_info: TypeInfo,
+    array_size: u32,
+    flags: ParameterFlags,
+    _value: PhantomData<&'v ()>
+}
+
+impl<'v> NodeArg<'v> {
+    #[inline]
+    pub fn image(image_view: ImageView) -> Self {
+        Self {
+            data: NodeArgData::Image(image_view),
+            type_info: ImageView::TYPE_INFO,
+            array_size: 0,
+            flags: ParameterFlags::RESOURCE,
+            _value: PhantomData
+        }
+    }
+
+    #[inline]
+    pub fn buffer(buffer_view: BufferView) -> Self {
+        Self {
+            data: NodeArgData::Buffer(buffer_view),
+            type_info: BufferView::TYPE_INFO,
+            array_size: 0,
+            flags: ParameterFlags::RESOURCE,
+            _value: PhantomData
+        }
+    }
+
+    #[inline]
+    pub fn value<T: RpsArg>(value: &'v T) -> Self {
+        Self {
+            data: NodeArgData::Value {
+                data: (value as *const T).cast(),
+                size: mem::size_of::<T>(),
+                alignment: mem::align_of::<T>()
+            },
+            type_info: T::TYPE_INFO,
+            array_size: T::ARRAY_SIZE,
+            flags: ParameterFlags::NONE,
+            _value: PhantomData
+        }
+    }
+
+    #[inline]
+    fn matches(&self, param: &DeclaredParam) -> bool {
+        self.type_info.size == param.type_info.size
+            && self.type_info.id == param.type_info.id
+            && self.array_size == param.array_size
+            && self.flags.contains(ParameterFlags::RESOURCE) == param.is_resource
+    }
+}
+
+struct DeclaredParam {
+    name: String,
+    type_info: TypeInfo,
+    array_size: u32,
+    is_resource: bool,
+    access: AccessAttr
+}
+
+struct DeclaredNode {
+    name: String,
+    params: Vec<DeclaredParam>
+}
+
+pub struct GraphBuilder<'b> {
+    handle: crate::RenderGraphBuilder,
+    node_decls: HashMap<NodeDeclId, DeclaredNode>,
+    next_local_id: ResourceId,
+    callbacks: &'b mut NodeCallbacks,
+    nodes: &'b mut NodeDiagnostics
+}
+
+impl<'b> GraphBuilder<'b> {
+    #[inline]
+    pub fn handle(&self) -> crate::RenderGraphBuilder {
+        self.handle
+    }
+
+    fn allocate<T>(&self, len: usize) -> RpsResult<NonNull<T>> {
+        if len == 0 || mem::size_of::<T>() == 0 {
+            return Ok(NonNull::dangling());
+        }
+
+        let data = unsafe { render_graph_allocate_data_for_type_with_len::<T>(self.handle, len) }?;
+        NonNull::new(data).ok_or_else(|| RpsError::OutOfMemory(ErrorContext::new("rpsRenderGraphAllocateDataAligned")))
+    }
+
+    fn allocate_bytes(&self, size: usize, alignment: usize) -> RpsResult<NonNull<u8>> {
+        let data = unsafe { render_graph_allocate_data_aligned(self.handle, size, alignment) };
+        NonNull::new(data.cast_mut().cast()).ok_or_else(|| RpsError::OutOfMemory(ErrorContext::new("rpsRenderGraphAllocateDataAligned")))
+    }
+
+    fn allocate_value<T: Copy>(&self, value: T) -> RpsResult<NonNull<T>> {
+        let data = self.allocate::<T>(1)?;
+        unsafe { data.as_ptr().write(value) };
+        Ok(data)
+    }
+
+    // Arena data lives until the render graph is updated again, which covers command recording for this frame.
+    // Node callbacks are 'static and cannot borrow it; pass values to nodes as NodeArg::value and read them
+    // back through CmdContext::arg, which points into the same arena.
+    #[inline]
+    pub fn alloc_value<T: Copy>(&self, value: T) -> RpsResult<&'b T> {
+        self.allocate_value(value).map(|data| unsafe { data.as_ref() })
+    }
+
+    pub fn alloc_slice<T: Copy>(&self, values: &[T]) -> RpsResult<&'b [T]> {
+        let data = self.allocate::<T>(values.len())?;
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), data.as_ptr(), values.len());
+            Ok(slice::from_raw_parts(data.as_ptr(), values.len()))
+        }
+    }
+
+    #[inline]
+    pub fn alloc_str(&self, value: &str) -> RpsResult<&'b str> {
+        self.alloc_slice(value.as_bytes()).map(|bytes| unsafe { str::from_utf8_unchecked(bytes) })
+    }
+
+    pub fn alloc_c_str(&self, value: &str) -> RpsResult<&'b CStr> {
+        if value.contains('\0') {
+            return Err(RpsError::InvalidArguments(ErrorContext::new("rpsRenderGraphAllocateDataAligned")).with_name(value));
+        }
+
+        let data = self.allocate_bytes(value.len() + 1, 1)?;
+        unsafe {
+            ptr::copy_nonoverlapping(value.as_ptr(), data.as_ptr(), value.len());
+            data.as_ptr().add(value.len()).write(0);
+            Ok(CStr::from_bytes_with_nul_unchecked(slice::from_raw_parts(data.as_ptr(), value.len() + 1)))
+        }
+    }
+
+    pub fn declare_node(&mut self, name: &str, flags: NodeDeclFlags, params: &[NodeParam]) -> RpsResult<NodeDeclId> {
+        let param_attrs = self.allocate::<ParamAttr>(params.len())?;
+        let param_descs = self.allocate::<ParameterDesc>(params.len())?;
+        for (index, param) in params.iter().enumerate() {
+            unsafe {
+                let attr = param_attrs.as_ptr().add(index);
+                attr.write(param.attr);
+                param_descs.as_ptr().add(index).write(ParameterDesc {
+                    type_info: param.type_info,
+                    array_size: param.array_size,
+                    attr: attr.cast_const().cast(),
+                    name: self.alloc_c_str(param.name)?.as_ptr(),
+                    flags: param.flags
+                });
+            }
+        }
+
+        let node_desc = self.allocate_value(NodeDesc {
+            flags,
+            num_params: params.len() as _,
+            param_descs: param_descs.as_ptr(),
+            name: self.alloc_c_str(name)?.as_ptr()
+        })?;
+
+        let node_decl_id = unsafe { render_graph_declare_dynamic_node(self.handle, node_desc.as_ptr()) };
+        if node_decl_id == NODEDECL_ID_INVALID {
+            return Err(RpsError::InvalidOperation(ErrorContext::new("rpsRenderGraphDeclareDynamicNode")).with_name(name));
+        }
+
+        let declared_params = params
+            .iter()
+            .map(|param| {
+                DeclaredParam {
+                    name: param.name.to_owned(),
+                    type_info: param.type_info,
+                    array_size: param.array_size,
+                    is_resource: param.flags.contains(ParameterFlags::RESOURCE),
+                    access: param.attr.access
+                }
+            })
+            .collect();
+        self.node_decls.insert(
+            node_decl_id,
+            DeclaredNode {
+                name: name.to_owned(),
+                params: declared_params
+            }
+        );
+        Ok(node_decl_id)
+    }
+
+    pub fn declare_static_node(&mut self, node: &'static StaticNodeDesc) -> RpsResult<NodeDeclId> {
+        let node_decl_id = unsafe { render_graph_declare_dynamic_node(self.handle, node.as_ptr()) };
+        if node_decl_id == NODEDECL_ID_INVALID {
+            return Err(RpsError::InvalidOperation(ErrorContext::new("rpsRenderGraphDeclareDynamicNode")).with_name(node.name()));
+        }
+
+        let declared_params = node
+            .params()
+            .iter()
+            .map(|param| {
+                DeclaredParam {
+                    name: unsafe { CStr::from_ptr(param.name) }.to_string_lossy().into_owned(),
+                    type_info: param.type_info,
+                    array_size: param.array_size,
+                    is_resource: param.flags.contains(ParameterFlags::RESOURCE),
+                    access: unsafe { param.attr.cast::<ParamAttr>().as_ref() }.map(|attr| attr.access).unwrap_or_default()
+                }
+            })
+            .collect();
+        self.node_decls.insert(
+            node_decl_id,
+            DeclaredNode {
+                name: node.name().to_owned(),
+                params: declared_params
+            }
+        );
+        Ok(node_decl_id)
+    }
+
+    pub fn declare_resource(&mut self, name: &str, desc: &ResourceDesc) -> RpsResult<ResourceHandle> {
+        let name_c = self.alloc_c_str(name)?.as_ptr();
+        let desc = self.allocate_value(*desc)?;
+
+        let resource_id = unsafe { render_graph_declare_resource(self.handle, name_c, self.next_local_id, desc.as_ptr().cast()) };
+        if resource_id == RESOURCE_ID_INVALID {
+            return Err(RpsError::InvalidOperation(ErrorContext::new("rpsRenderGraphDeclareResource")).with_name(name));
+        }
+
+        self.next_local_id += 1;
+        Ok(ResourceHandle(resource_id))
+    }
+
+    #[inline]
+    pub fn add_node<F, E>(&mut self, node_decl_id: NodeDeclId, args: &[NodeArg], callback: F) -> RpsResult<NodeId>
+    where
+        F: FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static,
+        E: Into<Result>
+    {
+        self.add_node_with_tag(node_decl_id, 0, args, callback)
+    }
+
+    pub fn add_node_with_tag<F, E>(&mut self, node_decl_id: NodeDeclId, user_tag: u32, args: &[NodeArg], callback: F) -> RpsResult<NodeId>
+    where
+        F: FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static,
+        E: Into<Result>
+    {
+        let node_decl = self
+            .node_decls
+            .get(&node_decl_id)
+            .ok_or_else(|| RpsError::UnknownNode(ErrorContext::new("rpsRenderGraphAddNode")))?;
+        let params = &node_decl.params;
+        if params.len() != args.len() {
+            return Err(RpsError::InvalidArguments(ErrorContext::new("rpsRenderGraphAddNode")));
+        }
+
+        if let Some(param) = params.iter().zip(args).find_map(|(param, arg)| (!arg.matches(param)).then_some(param)) {
+            return Err(RpsError::TypeMismatch(ErrorContext::new("rpsRenderGraphAddNode")).with_name(param.name.as_str()));
+        }
+
+        let node = NodeDiagnostic {
+            name: node_decl.name.clone(),
+            accesses: params
+                .iter()
+                .zip(args)
+                .filter_map(|(param, arg)| {
+                    let resource_index = match arg.data {
+                        NodeArgData::Image(image_view) => image_view.base.resource_id,
+                        NodeArgData::Buffer(buffer_view) => buffer_view.base.resource_id,
+                        NodeArgData::Value { .. } => return None
+                    };
+                    Some(NodeResourceAccess {
+                        resource_index,
+                        access: param.access
+                    })
+                })
+                .collect()
+        };
+
+        let arg_ptrs = self.allocate::<Variable>(args.len())?;
+        for (index, arg) in args.iter().enumerate() {
+            let data: Variable = match arg.data {
+                NodeArgData::Image(image_view) => self.allocate_value(image_view)?.as_ptr().cast(),
+                NodeArgData::Buffer(buffer_view) => self.allocate_value(buffer_view)?.as_ptr().cast(),
+                NodeArgData::Value { data, size, alignment } => {
+                    let copy = self.allocate_bytes(size.max(1), alignment)?;
+                    unsafe { ptr::copy_nonoverlapping(data, copy.as_ptr(), size) };
+                    copy.as_ptr().cast()
+                }
+            };
+            unsafe { arg_ptrs.as_ptr().add(index).write(data) };
+        }
+
+        let callback = Box::new(Mutex::new(callback));
+        let node_id = unsafe {
+            render_graph_add_node(
+                self.handle,
+                node_decl_id,
+                user_tag,
+                Some(node_callback::<F, E>),
+                &*callback as *const Mutex<F> as *mut c_void,
+                CmdCallbackFlags::NONE,
+                arg_ptrs.as_ptr(),
+                args.len() as _
+            )
+        };
+        if node_id == INDEX_NONE_U32 {
+            return Err(RpsError::InvalidOperation(ErrorContext::new("rpsRenderGraphAddNode")));
+        }
+
+        self.callbacks.push(callback);
+        self.nodes.insert(node_id, node);
+        Ok(node_id)
+    }
+}
+
+type BuildFn<'f> = dyn FnMut(&mut GraphBuilder<'_>) -> RpsResult<()> + 'f;
+
+struct BuildState<'s> {
+    build: &'s mut BuildFn<'s>,
+    callbacks: NodeCallbacks,
+    nodes: NodeDiagnostics,
+    error: Option<RpsError>
+}
+
+thread_local! {
+    static BUILD_STATE: Cell<*mut c_void> = const { Cell::new(ptr::null_mut()) };
+}
+
+unsafe extern "C" fn build_render_graph(builder: crate::RenderGraphBuilder, _args: *const Constant, _num_args: u32) -> Result {
+    let state = BUILD_STATE.get().cast::<BuildState<'_>>();
+    if state.is_null() {
+        return Result::INVALID_OPERATION;
+    }
+
+    let state = &mut *state;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        (state.build)(&mut GraphBuilder {
+            handle: builder,
+            node_decls: HashMap::new(),
+            next_local_id: 0,
+            callbacks: &mut state.callbacks,
+            nodes: &mut state.nodes
+        })
+    }));
+
+    match result {
+        Ok(Ok(())) => Result::OK,
+        Ok(Err(error)) => {
+            let error_code = error.code();
+            state.error = Some(error);
+            error_code
+        }
+        Err(_) => Result::INTERNAL_ERROR
+    }
+}
+
+pub(crate) unsafe fn update_with_builder(
+    render_graph: crate::RenderGraph,
+    mut update_info: crate::RenderGraphUpdateInfo,
+    build: &mut BuildFn<'_>,
+    callbacks: &mut NodeCallbacks,
+    nodes: &mut NodeDiagnostics
+) -> RpsResult<()> {
+    let mut state = BuildState {
+        build,
+        callbacks: Vec::new(),
+        nodes: NodeDiagnostics::new(),
+        error: None
+    };
+
+    update_info.pfn_build_callback = Some(build_render_graph);
+    let previous_state = BUILD_STATE.replace(&mut state as *mut BuildState<'_> as *mut c_void);
+    let result = render_graph_update(render_graph, &update_info);
+    BUILD_STATE.set(previous_state);
+
+    // RPS may keep pointers into the new callbacks even when the update fails, so they are only released by the next successful update.
+    if result.is_ok() {
+        *callbacks = state.callbacks;
+        *nodes = state.nodes;
+    } else {
+        callbacks.append(&mut state.callbacks);
+    }
+
+    match (result, state.error) {
+        (Err(_), Some(error)) => Err(error),
+        (result, _) => result
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    mem::ManuallyDrop,
+    path::{Path, PathBuf},
+    process,
+    time::SystemTime
+};
+
+use libloading::Library;
+
+use crate::{
+    rpsl_dynamic_library_init,
+    safe::{rpsl_entry_name, Device, JitModule, Program, RenderGraph, RenderGraphCreateBuilder},
+    ErrorContext, JITLibrary, PfnRpslDynLibInit, RpsError, RpsResult, RpslEntry
+};
+
+pub const DYNAMIC_LIBRARY_INIT_NAME: &[u8] = b"___rps_dyn_lib_init\0";
+
+#[derive(Clone, Copy)]
+pub enum RpslModuleSource<'a> {
+    Jit { library: &'a JITLibrary, path: &'a Path },
+    DynamicLibrary { path: &'a Path }
+}
+
+impl<'a> RpslModuleSource<'a> {
+    #[inline]
+    pub fn path(&self) -> &'a Path {
+        match *self {
+            Self::Jit { path, .. } | Self::DynamicLibrary { path } => path
+        }
+    }
+
+    fn modified(&self) -> RpsResult<SystemTime> {
+        fs::metadata(self.path()).and_then(|metadata| metadata.modified()).map_err(|error| {
+            RpsError::FileNotFound(ErrorContext::new("HotReloadGraph::poll"))
+                .with_name(self.path().display().to_string())
+                .with_source(error)
+        })
+    }
+}
+
+struct DynamicLibraryModule {
+    library: ManuallyDrop<Library>,
+    path: PathBuf
+}
+
+impl Drop for DynamicLibraryModule {
+    fn drop(&mut self) {
+        unsafe { ManuallyDrop::drop(&mut self.library) };
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+enum LoadedModule<'a> {
+    Jit(JitModule<'a>),
+    DynamicLibrary(DynamicLibraryModule)
+}
+
+fn load_error(api: &'static str, path: &Path, error: libloading::Error) -> RpsError {
+    RpsError::FileNotFound(ErrorContext::new(api)).with_name(path.display().to_string()).with_source(error)
+}
+
+impl<'a> LoadedModule<'a> {
+    fn load(source: &RpslModuleSource<'a>, module_name: &str, entry_name: &str, generation: u64) -> RpsResult<(Self, RpslEntry)> {
+        match *source {
+            RpslModuleSource::Jit { library, path } => {
+                let module = library.load_module(path)?;
+                let entry = module.entry(module_name, entry_name)?.handle();
+                Ok((Self::Jit(module), entry))
+            }
+            RpslModuleSource::DynamicLibrary { path } => {
+                let mut file_name = path.file_stem().map(OsString::from).unwrap_or_default();
+                file_name.push(format!("-{}-{generation}", process::id()));
+                let mut shadow_path = env::temp_dir().join(file_name);
+                if let Some(extension) = path.extension() {
+                    shadow_path.set_extension(extension);
+                }
+
+                fs::copy(path, &shadow_path).map_err(|error| {
+                    RpsError::FileNotFound(ErrorContext::new("HotReloadGraph::reload"))
+                        .with_name(path.display().to_string())
+                        .with_source(error)
+                })?;
+
+                let library = match unsafe { Library::new(&shadow_path) } {
+                    Ok(library) => library,
+                    Err(error) => {
+                        let _ = fs::remove_file(&shadow_path);
+                        return Err(load_error("HotReloadGraph::reload", path, error));
+                    }
+                };
+                let module = DynamicLibraryModule {
+                    library: ManuallyDrop::new(library),
+                    path: shadow_path
+                };
+
+                unsafe {
+                    let pfn_dyn_lib_init = *module
+                        .library
+                        .get::<PfnRpslDynLibInit>(DYNAMIC_LIBRARY_INIT_NAME)
+                        .map_err(|error| load_error("rpsRpslDynamicLibraryInit", path, error))?;
+                    rpsl_dynamic_library_init(pfn_dyn_lib_init)?;
+
+                    let symbol_name = rpsl_entry_name(module_name, entry_name)?;
+                    let entry = **module.library.get::<*const RpslEntry>(symbol_name.as_bytes_with_nul()).map_err(|error| {
+                        RpsError::KeyNotFound(ErrorContext::new("HotReloadGraph::reload"))
+                            .with_name(symbol_name.to_string_lossy())
+                            .with_source(error)
+                    })?;
+
+                    Ok((Self::DynamicLibrary(module), entry))
+                }
+            }
+        }
+    }
+}
+
+pub struct HotReloadGraph<'a> {
+    device: &'a Device,
+    source: RpslModuleSource<'a>,
+    module_name: String,
+    entry_name: String,
+    builder: RenderGraphCreateBuilder,
+    render_graph: RenderGraph<'a>,
+    module: LoadedModule<'a>,
+    modified: SystemTime,
+    generation: u64,
+    stale_bindings: Vec<String>
+}
+
+impl<'a> HotReloadGraph<'a> {
+    #[inline]
+    pub fn new(device: &'a Device, source: RpslModuleSource<'a>, module_name: &str, entry_name: &str, builder: RenderGraphCreateBuilder) -> RpsResult<Self> {
+        Self::with_bindings(device, source, module_name, entry_name, builder, |_| Ok(()))
+    }
+
+    pub fn with_bindings(
+        device: &'a Device,
+        source: RpslModuleSource<'a>,
+        module_name: &str,
+        entry_name: &str,
+        builder: RenderGraphCreateBuilder,
+        bind: impl FnOnce(&mut Program<'a>) -> RpsResult<()>
+    ) -> RpsResult<Self> {
+        let modified = source.modified()?;
+        let (module, entry) = LoadedModule::load(&source, module_name, entry_name, 0)?;
+        let render_graph = builder.build_with_bindings(device, entry, bind)?;
+
+        Ok(Self {
+            device,
+            source,
+            module_name: module_name.to_owned(),
+            entry_name: entry_name.to_owned(),
+            builder,
+            render_graph,
+            module,
+            modified,
+            generation: 0,
+            stale_bindings: Vec::new()
+        })
+    }
+
+    #[inline]
+    pub fn render_graph(&self) -> &RenderGraph<'a> {
+        &self.render_graph
+    }
+
+    #[inline]
+    pub fn render_graph_mut(&mut self) -> &mut RenderGraph<'a> {
+        &mut self.render_graph
+    }
+
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    // Nodes that were bound before the last reload but are no longer declared by the module.
+    #[inline]
+    pub fn stale_bindings(&self) -> &[String] {
+        &self.stale_bindings
+    }
+
+    #[inline]
+    pub fn jit_module(&self) -> Option<&JitModule<'a>> {
+        match &self.module {
+            LoadedModule::Jit(module) => Some(module),
+            LoadedModule::DynamicLibrary(_) => None
+        }
+    }
+
+    #[inline]
+    pub fn dynamic_library(&self) -> Option<&Library> {
+        match &self.module {
+            LoadedModule::Jit(_) => None,
+            LoadedModule::DynamicLibrary(module) => Some(&module.library)
+        }
+    }
+
+    pub fn poll(&mut self) -> RpsResult<bool> {
+        let modified = self.source.modified()?;
+        if modified == self.modified {
+            return Ok(false);
+        }
+
+        self.modified = modified;
+        self.reload()?;
+        Ok(true)
+    }
+
+    pub fn reload(&mut self) -> RpsResult<()> {
+        let generation = self.generation + 1;
+        let (module, entry) = LoadedModule::load(&self.source, &self.module_name, &self.entry_name, generation)?;
+
+        let previous_entry = self.render_graph.main_entry();
+        let mut stale_bindings = Vec::new();
+        let render_graph = self.builder.build_with_bindings(self.device, entry, |main_entry| {
+            stale_bindings = previous_entry.copy_bindings_to(main_entry)?;
+            Ok(())
+        })?;
+
+        self.render_graph = render_graph;
+        self.module = module;
+        self.generation = generation;
+        self.stale_bindings = stale_bindings;
+        Ok(())
+    }
+}
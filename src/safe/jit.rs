@@ -0,0 +1,102 @@
+use std::{
+    ffi::{c_char, CStr, CString},
+    marker::PhantomData,
+    path::Path
+};
+
+use crate::{make_rpsl_entry_name, result_from_ffi, ErrorContext, JITLibrary, JITModule, RpsError, RpsResult, RpslEntry};
+
+fn c_string(value: &str, api: &'static str) -> RpsResult<CString> {
+    CString::new(value).map_err(|error| RpsError::InvalidArguments(ErrorContext::new(api)).with_name(value).with_source(error))
+}
+
+pub fn rpsl_entry_name(module_name: &str, entry_name: &str) -> RpsResult<CString> {
+    let module_name_c = c_string(module_name, "rpsMakeRpslEntryName")?;
+    let entry_name_c = c_string(entry_name, "rpsMakeRpslEntryName")?;
+
+    let mut buffer = vec![0 as c_char; module_name.len() + entry_name.len() + 16];
+    let name = unsafe { make_rpsl_entry_name(buffer.as_mut_ptr(), buffer.len(), module_name_c.as_ptr(), entry_name_c.as_ptr()) };
+    if name.is_null() {
+        return Err(RpsError::InvalidArguments(ErrorContext::new("rpsMakeRpslEntryName")).with_name(format!("{module_name}::{entry_name}")));
+    }
+
+    Ok(unsafe { CStr::from_ptr(name) }.to_owned())
+}
+
+impl JITLibrary {
+    pub fn start(&self, args: &[&str]) -> RpsResult<()> {
+        let args = args.iter().map(|arg| c_string(arg, "RpsJITStartup")).collect::<RpsResult<Vec<_>>>()?;
+        let arg_ptrs = args.iter().map(|arg| arg.as_ptr()).collect::<Vec<_>>();
+
+        unsafe { result_from_ffi(self.startup(arg_ptrs.len() as _, arg_ptrs.as_ptr()), "RpsJITStartup") }
+    }
+
+    #[inline]
+    pub fn stop(mut self) {
+        unsafe { self.shutdown() };
+    }
+
+    pub fn load_module(&self, path: impl AsRef<Path>) -> RpsResult<JitModule<'_>> {
+        let path = path.as_ref();
+        let name = path
+            .to_str()
+            .ok_or_else(|| RpsError::InvalidArguments(ErrorContext::new("RpsJITLoad")).with_name(path.display().to_string()))?;
+        let name_c = c_string(name, "RpsJITLoad")?;
+
+        let handle = unsafe { self.load(name_c.as_ptr()) }.map_err(|error| error.with_name(name))?;
+
+        Ok(JitModule { library: self, handle })
+    }
+}
+
+pub struct JitModule<'a> {
+    library: &'a JITLibrary,
+    handle: JITModule
+}
+
+impl<'a> JitModule<'a> {
+    #[inline]
+    pub fn handle(&self) -> JITModule {
+        self.handle
+    }
+
+    #[inline]
+    pub fn library(&self) -> &'a JITLibrary {
+        self.library
+    }
+
+    pub fn entry(&self, module_name: &str, entry_name: &str) -> RpsResult<JitEntry<'_>> {
+        let symbol_name = rpsl_entry_name(module_name, entry_name)?;
+        let symbol_name_str = symbol_name.to_string_lossy();
+
+        let address = unsafe { self.library.get_entry_point(self.handle, symbol_name.as_ptr()) }.map_err(|error| error.with_name(symbol_name_str.as_ref()))?;
+        if address == 0 {
+            return Err(RpsError::KeyNotFound(ErrorContext::new("RpsJITGetEntryPoint")).with_name(symbol_name_str));
+        }
+
+        Ok(JitEntry {
+            entry: unsafe { *(address as usize as *const RpslEntry) },
+            _module: PhantomData
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct JitEntry<'m> {
+    entry: RpslEntry,
+    _module: PhantomData<&'m JitModule<'m>>
+}
+
+impl JitEntry<'_> {
+    #[inline]
+    pub fn handle(&self) -> RpslEntry {
+        self.entry
+    }
+}
+
+impl Drop for JitModule<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { self.library.unload(self.handle) };
+    }
+}
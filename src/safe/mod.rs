@@ -0,0 +1,41 @@
+mod allocator;
+mod arg;
+mod cmd;
+mod cpu_executor;
+mod device;
+mod diagnostics;
+mod frame_tracker;
+mod graph_args;
+mod graph_builder;
+mod hot_reload;
+mod jit;
+mod node;
+mod phase;
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod printer;
+mod program;
+mod render_graph;
+mod runtime_backend;
+mod signature;
+mod trace;
+
+pub use allocator::*;
+pub use arg::*;
+pub use cmd::*;
+pub use cpu_executor::*;
+pub use device::*;
+pub use diagnostics::*;
+pub use frame_tracker::*;
+pub use graph_args::*;
+pub use graph_builder::*;
+pub use hot_reload::*;
+pub use jit::*;
+pub use node::*;
+pub use phase::*;
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub use printer::*;
+pub use program::*;
+pub use render_graph::*;
+pub use runtime_backend::*;
+pub use signature::*;
+pub use trace::*;
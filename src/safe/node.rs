@@ -0,0 +1,200 @@
+use std::{ffi::CStr, slice};
+
+use crate::{AccessAttr, AccessFlags, NodeDeclFlags, NodeDesc, ParameterDesc, ShaderStage};
+
+#[repr(transparent)]
+pub struct StaticNodeDesc(NodeDesc);
+
+unsafe impl Send for StaticNodeDesc {}
+unsafe impl Sync for StaticNodeDesc {}
+
+impl StaticNodeDesc {
+    #[doc(hidden)]
+    #[inline]
+    pub const unsafe fn new(desc: NodeDesc) -> Self {
+        Self(desc)
+    }
+
+    #[inline]
+    pub fn desc(&self) -> &NodeDesc {
+        &self.0
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const NodeDesc {
+        &self.0
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        unsafe { CStr::from_ptr(self.0.name) }.to_str().unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn params(&self) -> &[ParameterDesc] {
+        if self.0.num_params == 0 {
+            return &[];
+        }
+
+        unsafe { slice::from_raw_parts(self.0.param_descs, self.0.num_params as _) }
+    }
+}
+
+#[doc(hidden)]
+pub const fn infer_node_decl_flags(accesses: &[AccessAttr]) -> NodeDeclFlags {
+    const GRAPHICS_ACCESS: AccessFlags = AccessFlags::RENDER_TARGET
+        .union(AccessFlags::DEPTH_STENCIL)
+        .union(AccessFlags::VERTEX_BUFFER)
+        .union(AccessFlags::INDEX_BUFFER)
+        .union(AccessFlags::STREAM_OUT)
+        .union(AccessFlags::SHADING_RATE);
+    const GRAPHICS_STAGES: ShaderStage = ShaderStage::VS
+        .union(ShaderStage::PS)
+        .union(ShaderStage::GS)
+        .union(ShaderStage::HS)
+        .union(ShaderStage::DS)
+        .union(ShaderStage::AS)
+        .union(ShaderStage::MS);
+    const COPY_ACCESS: AccessFlags = AccessFlags::COPY_SRC
+        .union(AccessFlags::COPY_DEST)
+        .union(AccessFlags::RESOLVE_SRC)
+        .union(AccessFlags::RESOLVE_DEST);
+
+    let mut graphics = false;
+    let mut compute = false;
+    let mut copy = false;
+
+    let mut index = 0;
+    while index < accesses.len() {
+        let access = accesses[index];
+        graphics |= access.access_flags.intersects(GRAPHICS_ACCESS) || access.access_stages.intersects(GRAPHICS_STAGES);
+        compute |= access.access_flags.intersects(AccessFlags::UNORDERED_ACCESS) || access.access_stages.intersects(ShaderStage::CS);
+        copy |= access.access_flags.intersects(COPY_ACCESS);
+        index += 1;
+    }
+
+    if graphics {
+        NodeDeclFlags::GRAPHICS
+    } else if compute {
+        NodeDeclFlags::COMPUTE
+    } else if copy {
+        NodeDeclFlags::COPY
+    } else {
+        NodeDeclFlags::NONE
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __node_access_attr {
+    () => {
+        $crate::AccessAttr {
+            access_flags: $crate::AccessFlags::UNKNOWN,
+            access_stages: $crate::ShaderStage::NONE
+        }
+    };
+    ($access: ident $(| $more_access: ident)* $(($($stage: ident),* $(,)?))?) => {
+        $crate::paste! {
+            $crate::AccessAttr {
+                access_flags: $crate::AccessFlags::[<$access:upper>] $(.union($crate::AccessFlags::[<$more_access:upper>]))*,
+                access_stages: $crate::ShaderStage::NONE $($(.union($crate::ShaderStage::[<$stage:upper>]))*)?
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __node_semantic_attr {
+    () => {
+        $crate::SemanticAttr {
+            semantic: $crate::Semantic::UNSPECIFIED,
+            semantic_index: 0
+        }
+    };
+    ($semantic: ident $([$semantic_index: literal])?) => {
+        $crate::paste! {
+            $crate::SemanticAttr {
+                semantic: $crate::Semantic::[<$semantic:upper>],
+                semantic_index: 0 $(+ $semantic_index)?
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __node_desc {
+    (
+        $vis: vis $static_name: ident [$($flag: ident),*] $name: ident
+        $($param: ident: $type_: ty $([$access: ident $(| $more_access: ident)* $(($($stage: ident),* $(,)?))?])? $(=> $semantic: ident $([$semantic_index: literal])?)?),* $(,)?
+    ) => {
+        $vis static $static_name: $crate::safe::StaticNodeDesc = {
+            const PARAM_DESCS: &[$crate::ParameterDesc] = &[$(
+                $crate::ParameterDesc {
+                    type_info: <$type_ as $crate::safe::RpsArg>::TYPE_INFO,
+                    array_size: <$type_ as $crate::safe::RpsArg>::ARRAY_SIZE,
+                    attr: {
+                        const ATTR: &$crate::ParamAttr = &$crate::ParamAttr {
+                            access: $crate::__node_access_attr!($($access $(| $more_access)* $(($($stage),*))?)?),
+                            semantic: $crate::__node_semantic_attr!($($semantic $([$semantic_index])?)?)
+                        };
+                        ATTR as *const $crate::ParamAttr as $crate::Constant
+                    },
+                    name: concat!(stringify!($param), "\0").as_ptr().cast(),
+                    flags: if <$type_ as $crate::safe::RpsArg>::IS_RESOURCE {
+                        $crate::ParameterFlags::RESOURCE
+                    } else {
+                        $crate::ParameterFlags::NONE
+                    }
+                }
+            ),*];
+
+            unsafe {
+                $crate::safe::StaticNodeDesc::new($crate::NodeDesc {
+                    flags: $crate::safe::infer_node_decl_flags(&[$($crate::__node_access_attr!($($access $(| $more_access)* $(($($stage),*))?)?)),*])
+                        $(.union($crate::paste!($crate::NodeDeclFlags::[<$flag:upper>])))*,
+                    num_params: PARAM_DESCS.len() as _,
+                    param_descs: PARAM_DESCS.as_ptr(),
+                    name: concat!(stringify!($name), "\0").as_ptr().cast()
+                })
+            }
+        };
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __node_adapter {
+    (
+        $vis: vis $adapter: ident
+        $($param: ident: $type_: ty $([$access: ident $(| $more_access: ident)* $(($($stage: ident),* $(,)?))?])? $(=> $semantic: ident $([$semantic_index: literal])?)?),* $(,)?
+    ) => {
+        $vis fn $adapter<F, E>(mut callback: F) -> impl FnMut(&mut $crate::safe::CmdContext<'_>) -> ::std::result::Result<(), $crate::Result> + Send + 'static
+        where
+            F: for<'c> FnMut(&mut $crate::safe::CmdContext<'c>, $(&'c $type_),*) -> ::std::result::Result<(), E> + Send + 'static,
+            E: Into<$crate::Result>
+        {
+            move |context| {
+                #[allow(unused_mut, unused_variables)]
+                let mut indices = 0..;
+                $(let $param = context.arg::<$type_>(indices.next().unwrap_or_default())?;)*
+                callback(context, $($param),*).map_err(Into::into)
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! node {
+    () => {};
+    ($vis: vis static $static_name: ident: $([$($flag: ident),* $(,)?])? fn $name: ident($($params: tt)*) => fn $adapter: ident; $($rest: tt)*) => {
+        $crate::__node_desc!($vis $static_name [$($($flag),*)?] $name $($params)*);
+        $crate::__node_adapter!($vis $adapter $($params)*);
+        $crate::node!($($rest)*);
+    };
+    ($vis: vis static $static_name: ident: $([$($flag: ident),* $(,)?])? fn $name: ident($($params: tt)*); $($rest: tt)*) => {
+        $crate::__node_desc!($vis $static_name [$($($flag),*)?] $name $($params)*);
+        $crate::node!($($rest)*);
+    };
+}
@@ -0,0 +1,64 @@
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc
+};
+
+use crate::{RenderGraphPhaseInfo, RenderGraphUpdateInfo, Result, RpsResult};
+
+pub trait CustomPhase: Send + 'static {
+    fn run(&mut self, render_graph: crate::RenderGraph, update_info: &RenderGraphUpdateInfo) -> RpsResult<()>;
+}
+
+type BoxedPhase = Box<dyn CustomPhase>;
+
+unsafe extern "C" fn run_phase(render_graph: crate::RenderGraph, update_info: *const RenderGraphUpdateInfo, phase: crate::RenderGraphPhase) -> Result {
+    let phase = &mut *phase.into_raw().cast::<BoxedPhase>();
+
+    match panic::catch_unwind(AssertUnwindSafe(|| phase.run(render_graph, &*update_info))) {
+        Ok(Ok(())) => Result::OK,
+        Ok(Err(error)) => error.into(),
+        Err(_) => Result::INTERNAL_ERROR
+    }
+}
+
+unsafe extern "C" fn destroy_phase(_phase: crate::RenderGraphPhase) {}
+
+pub(crate) struct PhaseInstance(Box<BoxedPhase>);
+
+impl PhaseInstance {
+    #[inline]
+    pub(crate) fn info(&mut self) -> RenderGraphPhaseInfo {
+        RenderGraphPhaseInfo {
+            phase: crate::RenderGraphPhase::from_raw((&mut *self.0 as *mut BoxedPhase).cast()),
+            pfn_run: Some(run_phase),
+            pfn_destroy: Some(destroy_phase)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct PhaseFactory(Arc<dyn Fn() -> BoxedPhase + Send + Sync>);
+
+impl PhaseFactory {
+    #[inline]
+    pub(crate) fn new<P, F>(factory: F) -> Self
+    where
+        P: CustomPhase,
+        F: Fn() -> P + Send + Sync + 'static
+    {
+        Self(Arc::new(move || Box::new(factory())))
+    }
+
+    #[inline]
+    pub(crate) fn create(&self) -> PhaseInstance {
+        PhaseInstance(Box::new((self.0)()))
+    }
+}
+
+impl fmt::Debug for PhaseFactory {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PhaseFactory")
+    }
+}
@@ -0,0 +1,123 @@
+use std::ffi::{c_char, c_void, CStr};
+
+use crate::{set_global_debug_printer, set_global_debug_printer_log_level, DiagLogLevel, Printer, VaList};
+
+// Messages are formatted in a single vsnprintf pass; a va_list can only be consumed once and there is no portable va_copy
+// on stable Rust. Longer messages are truncated.
+const MESSAGE_BUFFER_SIZE: usize = 4096;
+const TRUNCATION_MARKER: &str = "...";
+
+extern "C" {
+    fn vsnprintf(buffer: *mut c_char, size: usize, format: *const c_char, vl: VaList) -> i32;
+}
+
+unsafe fn format_message(format: *const c_char, vl: VaList) -> String {
+    let mut buffer = vec![0u8; MESSAGE_BUFFER_SIZE];
+    let len = vsnprintf(buffer.as_mut_ptr().cast(), buffer.len(), format, vl);
+    if len < 0 {
+        return CStr::from_ptr(format).to_string_lossy().into_owned();
+    }
+
+    let truncated = len as usize >= buffer.len();
+    buffer.truncate((len as usize).min(buffer.len() - 1));
+    let mut message = String::from_utf8_lossy(&buffer).into_owned();
+    if truncated {
+        message.push_str(TRUNCATION_MARKER);
+    }
+    message
+}
+
+unsafe fn emit_message(format: *const c_char, vl: VaList, emit: impl FnOnce(&str)) {
+    let message = format_message(format, vl);
+    let message = message.trim();
+    if !message.is_empty() {
+        emit(message);
+    }
+}
+
+// RPS passes no severity to the printer callbacks, only the formatted text, so every message is emitted at the level the
+// printer was created with.
+#[inline]
+fn level_from_context(context: *mut c_void) -> DiagLogLevel {
+    DiagLogLevel::from_raw(context as usize as u32)
+}
+
+#[inline]
+fn printer(pfn_vprintf: unsafe extern "C" fn(*mut c_void, *const c_char, VaList), level: DiagLogLevel) -> Printer {
+    Printer {
+        pfn_printf: None,
+        pfn_vprintf: Some(pfn_vprintf),
+        context: level.into_raw() as usize as *mut c_void
+    }
+}
+
+#[inline]
+fn set_global_printer(printer: Printer, min_log_level: DiagLogLevel) {
+    unsafe {
+        set_global_debug_printer(&printer);
+        set_global_debug_printer_log_level(min_log_level);
+    }
+}
+
+#[cfg(feature = "log")]
+#[inline]
+fn log_level(level: DiagLogLevel) -> log::Level {
+    match level {
+        DiagLogLevel::INFO => log::Level::Info,
+        DiagLogLevel::WARNING => log::Level::Warn,
+        _ => log::Level::Error
+    }
+}
+
+#[cfg(feature = "log")]
+unsafe extern "C" fn log_vprintf(context: *mut c_void, format: *const c_char, vl: VaList) {
+    let level = log_level(level_from_context(context));
+    if log::log_enabled!(target: "rps", level) {
+        emit_message(format, vl, |message| log::log!(target: "rps", level, "{message}"));
+    }
+}
+
+#[cfg(feature = "log")]
+#[inline]
+pub fn log_printer(level: DiagLogLevel) -> Printer {
+    printer(log_vprintf, level)
+}
+
+#[cfg(feature = "log")]
+#[inline]
+pub fn set_global_log_printer(min_log_level: DiagLogLevel) {
+    set_global_printer(log_printer(min_log_level), min_log_level);
+}
+
+#[cfg(feature = "tracing")]
+unsafe extern "C" fn tracing_vprintf(context: *mut c_void, format: *const c_char, vl: VaList) {
+    match level_from_context(context) {
+        DiagLogLevel::INFO => {
+            if tracing::enabled!(target: "rps", tracing::Level::INFO) {
+                emit_message(format, vl, |message| tracing::info!(target: "rps", "{message}"));
+            }
+        }
+        DiagLogLevel::WARNING => {
+            if tracing::enabled!(target: "rps", tracing::Level::WARN) {
+                emit_message(format, vl, |message| tracing::warn!(target: "rps", "{message}"));
+            }
+        }
+        _ => {
+            if tracing::enabled!(target: "rps", tracing::Level::ERROR) {
+                emit_message(format, vl, |message| tracing::error!(target: "rps", "{message}"));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[inline]
+pub fn tracing_printer(level: DiagLogLevel) -> Printer {
+    printer(tracing_vprintf, level)
+}
+
+#[cfg(feature = "tracing")]
+#[inline]
+pub fn set_global_tracing_printer(min_log_level: DiagLogLevel) {
+    set_global_printer(tracing_printer(min_log_level), min_log_level);
+}
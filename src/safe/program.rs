@@ -0,0 +1,224 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    ffi::CString,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+    sync::{Arc, Mutex, PoisonError}
+};
+
+use crate::{
+    cmd_callback_report_error, program_bind_node_callback, program_bind_node_subprogram, program_create, program_destroy,
+    safe::{CmdContext, Device, EntrySignature},
+    CmdCallback, CmdCallbackContext, CmdCallbackFlags, ErrorContext, ProgramCreateInfo, Result, RpsError, RpsResult, RpslEntry, Subprogram
+};
+
+pub(crate) unsafe extern "C" fn node_callback<F, E>(context: *const CmdCallbackContext)
+where
+    F: FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static,
+    E: Into<Result>
+{
+    let callback = &*((*context).cmd_callback_context as *const Mutex<F>);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut callback = callback.lock().unwrap_or_else(PoisonError::into_inner);
+        callback(&mut CmdContext::from_raw(context)).map_err(Into::into)
+    }));
+
+    let error_code = match result {
+        Ok(Ok(())) => return,
+        Ok(Err(error_code)) if error_code != Result::OK => error_code,
+        Ok(Err(_)) => Result::UNSPECIFIED,
+        Err(_) => Result::INTERNAL_ERROR
+    };
+
+    let _ = cmd_callback_report_error(context, error_code);
+}
+
+enum NodeBinding<'a> {
+    Callback { callback: CmdCallback, closure: Option<Arc<dyn Any + Send + Sync>> },
+    Subprogram(&'a Program<'a>)
+}
+
+pub struct Program<'a> {
+    handle: Subprogram,
+    owned: bool,
+    signature: EntrySignature,
+    has_default_node_callback: bool,
+    bindings: HashMap<String, NodeBinding<'a>>,
+    _device: PhantomData<&'a Device>
+}
+
+impl<'a> Program<'a> {
+    #[inline]
+    pub fn new(device: &'a Device, rpsl_entry_point: RpslEntry) -> RpsResult<Self> {
+        unsafe {
+            Self::with_create_info(
+                device,
+                &ProgramCreateInfo {
+                    signature_desc: ptr::null(),
+                    rpsl_entry_point,
+                    default_node_callback: CmdCallback::default()
+                }
+            )
+        }
+    }
+
+    #[inline]
+    pub unsafe fn with_create_info(device: &'a Device, create_info: &ProgramCreateInfo) -> RpsResult<Self> {
+        let signature = EntrySignature::from_create_info(create_info)?;
+        Ok(Self {
+            handle: program_create(device.handle(), create_info)?,
+            owned: true,
+            signature,
+            has_default_node_callback: create_info.default_node_callback.pfn_callback.is_some(),
+            bindings: HashMap::new(),
+            _device: PhantomData
+        })
+    }
+
+    #[inline]
+    pub(crate) fn main_entry(handle: Subprogram, signature: EntrySignature, default_node_callback: &CmdCallback) -> Self {
+        Self {
+            handle,
+            owned: false,
+            signature,
+            has_default_node_callback: default_node_callback.pfn_callback.is_some(),
+            bindings: HashMap::new(),
+            _device: PhantomData
+        }
+    }
+
+    #[inline]
+    pub fn handle(&self) -> Subprogram {
+        self.handle
+    }
+
+    #[inline]
+    pub fn signature(&self) -> &EntrySignature {
+        &self.signature
+    }
+
+    #[inline]
+    pub fn bind_node<F, E>(&mut self, name: &str, callback: F) -> RpsResult<()>
+    where
+        F: FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static,
+        E: Into<Result>
+    {
+        self.bind_node_with_flags(name, CmdCallbackFlags::NONE, callback)
+    }
+
+    pub fn bind_node_with_flags<F, E>(&mut self, name: &str, flags: CmdCallbackFlags, callback: F) -> RpsResult<()>
+    where
+        F: FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static,
+        E: Into<Result>
+    {
+        let callback = Arc::new(Mutex::new(callback));
+        let cmd_callback = CmdCallback {
+            pfn_callback: Some(node_callback::<F, E>),
+            user_context: Arc::as_ptr(&callback) as *mut _,
+            flags
+        };
+
+        unsafe {
+            self.bind(
+                name,
+                NodeBinding::Callback {
+                    callback: cmd_callback,
+                    closure: Some(callback)
+                }
+            )
+        }
+    }
+
+    #[inline]
+    pub unsafe fn bind_node_callback(&mut self, name: &str, callback: CmdCallback) -> RpsResult<()> {
+        self.bind(name, NodeBinding::Callback { callback, closure: None })
+    }
+
+    #[inline]
+    pub fn bind_node_subprogram(&mut self, name: &str, subprogram: &'a Program<'a>) -> RpsResult<()> {
+        unsafe { self.bind(name, NodeBinding::Subprogram(subprogram)) }
+    }
+
+    unsafe fn bind(&mut self, name: &str, binding: NodeBinding<'a>) -> RpsResult<()> {
+        let api = match binding {
+            NodeBinding::Callback { .. } => "rpsProgramBindNodeCallback",
+            NodeBinding::Subprogram(_) => "rpsProgramBindNodeSubprogram"
+        };
+        let name_c = CString::new(name).map_err(|error| RpsError::InvalidArguments(ErrorContext::new(api)).with_name(name).with_source(error))?;
+        match &binding {
+            NodeBinding::Callback { callback, .. } => program_bind_node_callback(self.handle, name_c.as_ptr(), callback)?,
+            NodeBinding::Subprogram(subprogram) => program_bind_node_subprogram(self.handle, name_c.as_ptr(), subprogram.handle)?
+        }
+
+        self.bindings.insert(name.to_owned(), binding);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_node_bound(&self, name: &str) -> bool {
+        self.has_default_node_callback || self.bindings.contains_key(name)
+    }
+
+    // Nodes of bound subprograms are included, named `<node>/<subprogram node>`.
+    pub fn unbound_nodes(&self) -> Vec<String> {
+        let mut unbound_nodes = Vec::new();
+        for node in &self.signature.nodes {
+            match self.bindings.get(&node.name) {
+                Some(NodeBinding::Subprogram(subprogram)) => {
+                    unbound_nodes.extend(subprogram.unbound_nodes().into_iter().map(|sub_node| format!("{}/{sub_node}", node.name)));
+                }
+                Some(NodeBinding::Callback { .. }) => {}
+                None if self.has_default_node_callback => {}
+                None => unbound_nodes.push(node.name.clone())
+            }
+        }
+        unbound_nodes
+    }
+
+    pub fn validate_bindings(&self) -> RpsResult<()> {
+        let unbound_nodes = self.unbound_nodes();
+        if unbound_nodes.is_empty() {
+            Ok(())
+        } else {
+            Err(RpsError::ValidationFailed(ErrorContext::new("Program::validate_bindings")).with_name(unbound_nodes.join(", ")))
+        }
+    }
+
+    // Binds every node bound here to the same callback or subprogram in `target`; closures are shared, so both programs
+    // keep them alive. Bindings for nodes that `target` does not declare are skipped and their names returned.
+    pub fn copy_bindings_to(&self, target: &mut Program<'a>) -> RpsResult<Vec<String>> {
+        let mut stale_bindings = Vec::new();
+        for (name, binding) in &self.bindings {
+            if !target.signature.nodes.iter().any(|node| node.name == *name) {
+                stale_bindings.push(name.clone());
+                continue;
+            }
+
+            let binding = match binding {
+                NodeBinding::Callback { callback, closure } => {
+                    NodeBinding::Callback {
+                        callback: *callback,
+                        closure: closure.clone()
+                    }
+                }
+                &NodeBinding::Subprogram(subprogram) => NodeBinding::Subprogram(subprogram)
+            };
+            unsafe { target.bind(name, binding) }?;
+        }
+
+        stale_bindings.sort_unstable();
+        Ok(stale_bindings)
+    }
+}
+
+impl Drop for Program<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe { program_destroy(self.handle) };
+        }
+    }
+}
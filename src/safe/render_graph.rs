@@ -0,0 +1,379 @@
+use std::{ptr, slice};
+
+use crate::{
+    render_graph_create, render_graph_destroy, render_graph_execute, render_graph_get_batch_layout, render_graph_get_diagnostics_info, render_graph_get_main_entry,
+    render_graph_record_commands, render_graph_update,
+    safe::{
+        graph_builder::{update_with_builder, NodeCallbacks},
+        phase::{PhaseFactory, PhaseInstance},
+        CustomPhase, Device, Diagnostics, EntrySignature, GraphArgs, GraphBuilder, NodeDiagnostics, Program, RpsArg
+    },
+    CmdCallback, CommandBatch, Constant, DiagnosticFlags, ErrorContext, ProgramCreateInfo, QueueFlags, RandomNumberGenerator, RenderGraphBatchLayout, RenderGraphCreateInfo,
+    RenderGraphCreateMemoryInfo, RenderGraphCreateScheduleInfo, RenderGraphDiagnosticInfoFlags, RenderGraphExecuteInfo, RenderGraphFlags, RenderGraphRecordCommandInfo,
+    RenderGraphSignatureDesc, RenderGraphUpdateInfo, ResourceDesc, RpsError, RpsResult, RpslEntry, RuntimeCommandBuffer, RuntimeResource, ScheduleFlags, GPU_COMPLETED_FRAME_INDEX_NONE
+};
+
+#[inline]
+fn slice_ptr<T>(slice: &[T]) -> *const T {
+    if slice.is_empty() {
+        ptr::null()
+    } else {
+        slice.as_ptr()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RenderGraphCreateBuilder {
+    schedule_flags: ScheduleFlags,
+    queues: Vec<QueueFlags>,
+    heap_budget_mibs: Vec<u32>,
+    render_graph_flags: RenderGraphFlags,
+    default_node_callback: CmdCallback,
+    strict_node_bindings: bool,
+    phases: Vec<PhaseFactory>,
+    replace_default_phases: bool
+}
+
+impl RenderGraphCreateBuilder {
+    #[inline]
+    pub fn schedule_flags(mut self, schedule_flags: ScheduleFlags) -> Self {
+        self.schedule_flags = schedule_flags;
+        self
+    }
+
+    #[inline]
+    pub fn queues(mut self, queues: &[QueueFlags]) -> Self {
+        self.queues = queues.to_vec();
+        self
+    }
+
+    #[inline]
+    pub fn heap_budget_mibs(mut self, heap_budget_mibs: &[u32]) -> Self {
+        self.heap_budget_mibs = heap_budget_mibs.to_vec();
+        self
+    }
+
+    #[inline]
+    pub fn flags(mut self, render_graph_flags: RenderGraphFlags) -> Self {
+        self.render_graph_flags = render_graph_flags;
+        self
+    }
+
+    #[inline]
+    pub unsafe fn default_node_callback(mut self, default_node_callback: CmdCallback) -> Self {
+        self.default_node_callback = default_node_callback;
+        self
+    }
+
+    #[inline]
+    pub fn strict_node_bindings(mut self, strict_node_bindings: bool) -> Self {
+        self.strict_node_bindings = strict_node_bindings;
+        self
+    }
+
+    // RPS runs the custom phases instead of the runtime's default scheduling and recording phases, so
+    // registering a phase requires opting in with replace_default_phases.
+    #[inline]
+    pub fn replace_default_phases(mut self, replace_default_phases: bool) -> Self {
+        self.replace_default_phases = replace_default_phases;
+        self
+    }
+
+    #[inline]
+    pub fn phase<P, F>(mut self, factory: F) -> Self
+    where
+        P: CustomPhase,
+        F: Fn() -> P + Send + Sync + 'static
+    {
+        self.phases.push(PhaseFactory::new(factory));
+        self
+    }
+
+    #[inline]
+    pub fn build<'a>(&self, device: &'a Device, rpsl_entry_point: RpslEntry) -> RpsResult<RenderGraph<'a>> {
+        self.build_with_bindings(device, rpsl_entry_point, |_| Ok(()))
+    }
+
+    #[inline]
+    pub fn build_with_bindings<'a>(&self, device: &'a Device, rpsl_entry_point: RpslEntry, bind: impl FnOnce(&mut Program<'a>) -> RpsResult<()>) -> RpsResult<RenderGraph<'a>> {
+        unsafe { self.create(device, ptr::null(), rpsl_entry_point, bind) }
+    }
+
+    #[inline]
+    pub fn build_with_signature<'a>(&self, device: &'a Device, signature_desc: &RenderGraphSignatureDesc) -> RpsResult<RenderGraph<'a>> {
+        self.build_with_signature_and_bindings(device, signature_desc, |_| Ok(()))
+    }
+
+    #[inline]
+    pub fn build_with_signature_and_bindings<'a>(
+        &self,
+        device: &'a Device,
+        signature_desc: &RenderGraphSignatureDesc,
+        bind: impl FnOnce(&mut Program<'a>) -> RpsResult<()>
+    ) -> RpsResult<RenderGraph<'a>> {
+        unsafe { self.create(device, signature_desc, RpslEntry::null(), bind) }
+    }
+
+    unsafe fn create<'a>(
+        &self,
+        device: &'a Device,
+        signature_desc: *const RenderGraphSignatureDesc,
+        rpsl_entry_point: RpslEntry,
+        bind: impl FnOnce(&mut Program<'a>) -> RpsResult<()>
+    ) -> RpsResult<RenderGraph<'a>> {
+        if !self.phases.is_empty() && !self.replace_default_phases {
+            return Err(RpsError::InvalidOperation(ErrorContext::new("RenderGraphCreateBuilder::phase")));
+        }
+
+        let mut phases = self.phases.iter().map(PhaseFactory::create).collect::<Vec<_>>();
+        let phase_infos = phases.iter_mut().map(PhaseInstance::info).collect::<Vec<_>>();
+        let create_info = RenderGraphCreateInfo {
+            schedule_info: RenderGraphCreateScheduleInfo {
+                schedule_flags: self.schedule_flags,
+                num_queues: self.queues.len() as _,
+                queue_infos: slice_ptr(&self.queues)
+            },
+            memory_info: RenderGraphCreateMemoryInfo {
+                num_heaps: self.heap_budget_mibs.len() as _,
+                heap_budget_mibs: slice_ptr(&self.heap_budget_mibs)
+            },
+            main_entry_create_info: ProgramCreateInfo {
+                signature_desc,
+                rpsl_entry_point,
+                default_node_callback: self.default_node_callback
+            },
+            render_graph_flags: self.render_graph_flags,
+            num_phases: phase_infos.len() as _,
+            phases: slice_ptr(&phase_infos)
+        };
+
+        let signature = EntrySignature::from_create_info(&create_info.main_entry_create_info)?;
+        let handle = render_graph_create(device.handle(), &create_info)?;
+        let mut render_graph = RenderGraph {
+            handle,
+            main_entry: Program::main_entry(render_graph_get_main_entry(handle), signature, &self.default_node_callback),
+            node_callbacks: NodeCallbacks::new(),
+            nodes: NodeDiagnostics::new(),
+            strict_node_bindings: self.strict_node_bindings,
+            _phases: phases
+        };
+
+        bind(&mut render_graph.main_entry)?;
+        render_graph.validate_bindings()?;
+        Ok(render_graph)
+    }
+}
+
+pub struct FrameUpdate<'u> {
+    pub frame_index: u64,
+    pub gpu_completed_frame_index: u64,
+    pub schedule_flags: ScheduleFlags,
+    pub diagnostic_flags: DiagnosticFlags,
+    args: Vec<Constant>,
+    arg_resources: Vec<*const RuntimeResource>,
+    random_number_generator: Option<&'u RandomNumberGenerator>
+}
+
+impl<'u> FrameUpdate<'u> {
+    #[inline]
+    pub fn new(frame_index: u64) -> Self {
+        Self {
+            frame_index,
+            gpu_completed_frame_index: GPU_COMPLETED_FRAME_INDEX_NONE,
+            schedule_flags: ScheduleFlags::empty(),
+            diagnostic_flags: DiagnosticFlags::empty(),
+            args: Vec::new(),
+            arg_resources: Vec::new(),
+            random_number_generator: None
+        }
+    }
+
+    #[inline]
+    pub fn gpu_completed_frame_index(mut self, gpu_completed_frame_index: u64) -> Self {
+        self.gpu_completed_frame_index = gpu_completed_frame_index;
+        self
+    }
+
+    #[inline]
+    pub fn schedule_flags(mut self, schedule_flags: ScheduleFlags) -> Self {
+        self.schedule_flags = schedule_flags;
+        self
+    }
+
+    #[inline]
+    pub fn diagnostic_flags(mut self, diagnostic_flags: DiagnosticFlags) -> Self {
+        self.diagnostic_flags = diagnostic_flags;
+        self
+    }
+
+    #[inline]
+    pub fn random_number_generator(mut self, random_number_generator: &'u RandomNumberGenerator) -> Self {
+        self.random_number_generator = Some(random_number_generator);
+        self
+    }
+
+    #[inline]
+    pub fn arg<T: RpsArg>(mut self, value: &'u T) -> Self {
+        self.args.push((value as *const T).cast());
+        self.arg_resources.push(ptr::null());
+        self
+    }
+
+    #[inline]
+    pub fn resource_arg(mut self, resource_desc: &'u ResourceDesc, resources: &'u [RuntimeResource]) -> Self {
+        self.args.push((resource_desc as *const ResourceDesc).cast());
+        self.arg_resources.push(slice_ptr(resources));
+        self
+    }
+
+    pub fn graph_args(mut self, graph_args: &'u GraphArgs) -> RpsResult<Self> {
+        graph_args.validate()?;
+        self.args.clear();
+        self.arg_resources.clear();
+        for (arg, arg_resources) in graph_args.raw_args() {
+            self.args.push(arg);
+            self.arg_resources.push(arg_resources);
+        }
+
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn num_args(&self) -> usize {
+        self.args.len()
+    }
+
+    fn update_info(&self) -> RenderGraphUpdateInfo {
+        RenderGraphUpdateInfo {
+            frame_index: self.frame_index,
+            gpu_completed_frame_index: self.gpu_completed_frame_index,
+            schedule_flags: self.schedule_flags,
+            diagnostic_flags: self.diagnostic_flags,
+            num_args: self.args.len() as _,
+            args: slice_ptr(&self.args),
+            arg_resources: slice_ptr(&self.arg_resources),
+            pfn_build_callback: None,
+            random_number_generator: self.random_number_generator.map_or(ptr::null(), |random_number_generator| random_number_generator)
+        }
+    }
+}
+
+pub struct RenderGraph<'a> {
+    handle: crate::RenderGraph,
+    main_entry: Program<'a>,
+    node_callbacks: NodeCallbacks,
+    nodes: NodeDiagnostics,
+    strict_node_bindings: bool,
+    _phases: Vec<PhaseInstance>
+}
+
+impl<'a> RenderGraph<'a> {
+    #[inline]
+    pub fn builder() -> RenderGraphCreateBuilder {
+        RenderGraphCreateBuilder::default()
+    }
+
+    #[inline]
+    pub fn handle(&self) -> crate::RenderGraph {
+        self.handle
+    }
+
+    #[inline]
+    pub fn main_entry(&self) -> &Program<'a> {
+        &self.main_entry
+    }
+
+    #[inline]
+    pub fn main_entry_mut(&mut self) -> &mut Program<'a> {
+        &mut self.main_entry
+    }
+
+    #[inline]
+    pub fn unbound_nodes(&self) -> Vec<String> {
+        self.main_entry.unbound_nodes()
+    }
+
+    #[inline]
+    fn validate_bindings(&self) -> RpsResult<()> {
+        if self.strict_node_bindings {
+            self.main_entry.validate_bindings()
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    pub fn update(&mut self, frame_update: &FrameUpdate) -> RpsResult<()> {
+        self.validate_bindings()?;
+        unsafe { render_graph_update(self.handle, &frame_update.update_info()) }?;
+        self.nodes.clear();
+        Ok(())
+    }
+
+    #[inline]
+    pub fn update_with(&mut self, frame_update: &FrameUpdate, mut build: impl FnMut(&mut GraphBuilder<'_>) -> RpsResult<()>) -> RpsResult<()> {
+        self.validate_bindings()?;
+        unsafe { update_with_builder(self.handle, frame_update.update_info(), &mut build, &mut self.node_callbacks, &mut self.nodes) }
+    }
+
+    #[inline]
+    pub fn batch_layout(&self) -> RpsResult<RenderGraphBatchLayout> {
+        unsafe { render_graph_get_batch_layout(self.handle) }
+    }
+
+    #[inline]
+    pub fn diagnostics(&self, flags: RenderGraphDiagnosticInfoFlags) -> RpsResult<Diagnostics<'_>> {
+        unsafe {
+            let info = render_graph_get_diagnostics_info(self.handle, flags)?;
+            let batch_layout = render_graph_get_batch_layout(self.handle).ok();
+            Ok(Diagnostics::from_raw(&info, batch_layout.as_ref(), &self.nodes))
+        }
+    }
+
+    // Valid until the next update.
+    #[inline]
+    pub fn command_batches(&self) -> RpsResult<&[CommandBatch]> {
+        let batch_layout = self.batch_layout()?;
+        if batch_layout.cmd_batches.is_null() || batch_layout.num_cmd_batches == 0 {
+            Ok(&[])
+        } else {
+            Ok(unsafe { slice::from_raw_parts(batch_layout.cmd_batches, batch_layout.num_cmd_batches as _) })
+        }
+    }
+
+    // Safety: `record_info.cmd_buffer` must be a command buffer of the device's runtime that is ready for recording (runtimes
+    // that record without one, such as the null runtime, accept null), `record_info.user_context` must be valid for every
+    // node callback in the range, and the command range must lie within one batch of the last successful update.
+    #[inline]
+    pub unsafe fn record_commands(&self, record_info: &RenderGraphRecordCommandInfo) -> RpsResult<()> {
+        render_graph_record_commands(self.handle, record_info)
+    }
+
+    // Records every batch of the last update with the given command buffer, under the same conditions as `record_commands`.
+    pub unsafe fn record_all_commands(&self, cmd_buffer: RuntimeCommandBuffer, frame_index: u64) -> RpsResult<()> {
+        for batch in self.command_batches()? {
+            self.record_commands(&RenderGraphRecordCommandInfo {
+                cmd_buffer,
+                frame_index,
+                cmd_begin_index: batch.cmd_begin,
+                num_cmds: batch.num_cmds,
+                ..Default::default()
+            })?;
+        }
+        Ok(())
+    }
+
+    // Safety: the acquire and submit callbacks must hand out command buffers of the device's runtime and stay callable with
+    // `execute_info.user_context` for the duration of the call.
+    #[inline]
+    pub unsafe fn execute(&self, execute_info: &RenderGraphExecuteInfo) -> RpsResult<()> {
+        render_graph_execute(self.handle, execute_info)
+    }
+}
+
+impl Drop for RenderGraph<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { render_graph_destroy(self.handle) };
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    ffi::{c_char, c_void, CStr},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+    sync::{Arc, Mutex, PoisonError}
+};
+
+use crate::{
+    null_runtime_device_create, AccessAttr, ClearValue, Constant, DeviceCreateInfo, Format, GpuMemoryRequirement, HeapPlacement, NullRuntimeDeviceCreateInfo, RenderGraphPhaseInfo,
+    ResourceDesc, ResourceId, ResourceType, Result, RpsResult, RuntimeCallbacks, RuntimeCommandBuffer, RuntimeDebugMarkerMode, RuntimeDeviceCreateInfo, RuntimeHeap,
+    RuntimeOpCreateHeapArgs, RuntimeOpCreateNodeUserResourcesArgs, RuntimeOpCreateResourceArgs, RuntimeOpDestroyHeapArgs, RuntimeOpDestroyResourceArgs, RuntimeOpRecordDebugMarkerArgs,
+    RuntimeOpSetDebugNameArgs, RuntimeResource, Variable
+};
+
+#[inline]
+unsafe fn raw_slice<'a, T>(data: *const T, len: u32) -> &'a [T] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len as _)
+    }
+}
+
+#[inline]
+unsafe fn raw_str<'a>(name: *const c_char) -> Option<&'a str> {
+    if name.is_null() {
+        None
+    } else {
+        CStr::from_ptr(name).to_str().ok()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CreateHeapArgs<'a> {
+    pub memory_type_index: u32,
+    pub size: usize,
+    pub alignment: usize,
+    pub debug_name: Option<&'a str>
+}
+
+#[derive(Clone, Copy)]
+pub struct CreateResourceArgs<'a> {
+    pub resource_id: ResourceId,
+    pub desc: &'a ResourceDesc,
+    pub original_desc: Variable,
+    pub clear_value: &'a ClearValue,
+    pub alloc_requirement: GpuMemoryRequirement,
+    pub alloc_placement: HeapPlacement,
+    pub all_accesses: AccessAttr,
+    pub initial_access: AccessAttr,
+    pub mutable_formats: &'a [Format],
+    pub buffer_formatted_write: bool,
+    pub buffer_formatted_read: bool
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CreateNodeResourcesArgs<'a> {
+    pub user_context: *const c_void,
+    pub args: &'a [Constant],
+    pub node_tag: u32
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DebugMarkerArgs<'a> {
+    pub command_buffer: RuntimeCommandBuffer,
+    pub user_record_context: *const c_void,
+    pub mode: RuntimeDebugMarkerMode,
+    pub text: Option<&'a str>
+}
+
+pub trait RuntimeBackend: Send + 'static {
+    // When this returns false the runtime keeps its default phases and build_render_graph_phases is never called.
+    #[inline]
+    fn provides_render_graph_phases(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn build_render_graph_phases(&mut self, _render_graph: crate::RenderGraph) -> RpsResult<&[RenderGraphPhaseInfo]> {
+        Ok(&[])
+    }
+
+    #[inline]
+    fn create_heap(&mut self, _args: &CreateHeapArgs<'_>) -> RpsResult<RuntimeHeap> {
+        Ok(RuntimeHeap::null())
+    }
+
+    #[inline]
+    fn destroy_heaps(&mut self, _heaps: &[RuntimeHeap]) {}
+
+    #[inline]
+    fn create_resource(&mut self, _args: &CreateResourceArgs<'_>) -> RpsResult<RuntimeResource> {
+        Ok(RuntimeResource::null())
+    }
+
+    #[inline]
+    fn destroy_resources(&mut self, _resource_type: ResourceType, _resources: &[RuntimeResource]) {}
+
+    #[inline]
+    fn create_node_resources(&mut self, _args: &CreateNodeResourcesArgs<'_>) -> RpsResult<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn destroy_node_resources(&mut self) {}
+
+    #[inline]
+    fn record_debug_marker(&mut self, _args: &DebugMarkerArgs<'_>) {}
+
+    #[inline]
+    fn set_debug_name(&mut self, _resource: RuntimeResource, _resource_type: ResourceType, _name: &str) {}
+}
+
+type SharedBackend = Mutex<Box<dyn RuntimeBackend>>;
+
+unsafe fn with_backend<R>(user_context: *mut c_void, default: R, f: impl FnOnce(&mut dyn RuntimeBackend) -> R) -> R {
+    let backend = &*user_context.cast::<SharedBackend>();
+    panic::catch_unwind(AssertUnwindSafe(|| f(&mut **backend.lock().unwrap_or_else(PoisonError::into_inner)))).unwrap_or(default)
+}
+
+#[inline]
+fn into_result(result: RpsResult<()>) -> Result {
+    match result {
+        Ok(()) => Result::OK,
+        Err(error) => error.into()
+    }
+}
+
+unsafe extern "C" fn build_render_graph_phases(
+    user_context: *mut c_void,
+    render_graph: crate::RenderGraph,
+    phase_info: *const *const RenderGraphPhaseInfo,
+    num_phases: *mut u32
+) -> Result {
+    with_backend(user_context, Result::INTERNAL_ERROR, |backend| {
+        into_result(backend.build_render_graph_phases(render_graph).map(|phases| {
+            *phase_info.cast_mut() = if phases.is_empty() { ptr::null() } else { phases.as_ptr() };
+            *num_phases = phases.len() as _;
+        }))
+    })
+}
+
+unsafe extern "C" fn destroy_runtime(user_context: *mut c_void) {
+    drop(Arc::from_raw(user_context.cast::<SharedBackend>()));
+}
+
+unsafe extern "C" fn create_heap(user_context: *mut c_void, args: *const RuntimeOpCreateHeapArgs) -> Result {
+    let args = &*args;
+    let create_args = CreateHeapArgs {
+        memory_type_index: args.memory_type_index,
+        size: args.size,
+        alignment: args.alignment,
+        debug_name: raw_str(args.debug_name)
+    };
+
+    with_backend(user_context, Result::INTERNAL_ERROR, |backend| {
+        into_result(backend.create_heap(&create_args).map(|heap| *args.runtime_heap = heap))
+    })
+}
+
+unsafe extern "C" fn destroy_heap(user_context: *mut c_void, args: *const RuntimeOpDestroyHeapArgs) {
+    let heaps = raw_slice((*args).heaps, (*args).num_heaps);
+    with_backend(user_context, (), |backend| backend.destroy_heaps(heaps));
+}
+
+unsafe extern "C" fn create_resource(user_context: *mut c_void, args: *const RuntimeOpCreateResourceArgs) -> Result {
+    let args = &*args;
+    let create_args = CreateResourceArgs {
+        resource_id: args.resource_id,
+        desc: &args.desc,
+        original_desc: args.original_desc,
+        clear_value: &args.clear_value,
+        alloc_requirement: args.alloc_requirement,
+        alloc_placement: args.alloc_placement,
+        all_accesses: args.all_accesses,
+        initial_access: args.initial_access,
+        mutable_formats: raw_slice(args.mutable_formats, args.num_mutable_formats),
+        buffer_formatted_write: args.buffer_formatted_write != 0,
+        buffer_formatted_read: args.buffer_formatted_read != 0
+    };
+
+    with_backend(user_context, Result::INTERNAL_ERROR, |backend| {
+        into_result(backend.create_resource(&create_args).map(|resource| *args.runtime_resource = resource))
+    })
+}
+
+unsafe extern "C" fn destroy_resource(user_context: *mut c_void, args: *const RuntimeOpDestroyResourceArgs) {
+    let args = &*args;
+    let resources = raw_slice(args.runtime_resources, args.num_resources);
+    with_backend(user_context, (), |backend| backend.destroy_resources(args.resource_type, resources));
+}
+
+unsafe extern "C" fn create_node_resources(user_context: *mut c_void, args: *const RuntimeOpCreateNodeUserResourcesArgs) -> Result {
+    let args = &*args;
+    let create_args = CreateNodeResourcesArgs {
+        user_context: args.user_context,
+        args: raw_slice(args.args, args.num_args),
+        node_tag: args.node_tag
+    };
+
+    with_backend(user_context, Result::INTERNAL_ERROR, |backend| into_result(backend.create_node_resources(&create_args)))
+}
+
+unsafe extern "C" fn destroy_node_resources(user_context: *mut c_void) {
+    with_backend(user_context, (), |backend| backend.destroy_node_resources());
+}
+
+unsafe extern "C" fn record_debug_marker(user_context: *mut c_void, args: *const RuntimeOpRecordDebugMarkerArgs) {
+    let args = &*args;
+    let marker_args = DebugMarkerArgs {
+        command_buffer: args.command_buffer,
+        user_record_context: args.user_record_context,
+        mode: args.mode,
+        text: raw_str(args.text)
+    };
+
+    with_backend(user_context, (), |backend| backend.record_debug_marker(&marker_args));
+}
+
+unsafe extern "C" fn set_debug_name(user_context: *mut c_void, args: *const RuntimeOpSetDebugNameArgs) {
+    let args = &*args;
+    let name = raw_str(args.name).unwrap_or_default();
+    with_backend(user_context, (), |backend| backend.set_debug_name(args.resource, args.resource_type, name));
+}
+
+pub struct RuntimeBackendHost {
+    backend: Arc<SharedBackend>,
+    provides_phases: bool
+}
+
+impl RuntimeBackendHost {
+    #[inline]
+    pub fn new(backend: Box<dyn RuntimeBackend>) -> Self {
+        let provides_phases = backend.provides_render_graph_phases();
+        Self {
+            backend: Arc::new(Mutex::new(backend)),
+            provides_phases
+        }
+    }
+
+    #[inline]
+    fn create_info(&self, user_context: *const SharedBackend) -> RuntimeDeviceCreateInfo {
+        RuntimeDeviceCreateInfo {
+            user_context: user_context.cast_mut().cast(),
+            callbacks: RuntimeCallbacks {
+                pfn_build_render_graph_phases: if self.provides_phases { Some(build_render_graph_phases) } else { None },
+                pfn_destroy_runtime: Some(destroy_runtime),
+                pfn_create_heap: Some(create_heap),
+                pfn_destroy_heap: Some(destroy_heap),
+                pfn_create_resource: Some(create_resource),
+                pfn_destroy_resource: Some(destroy_resource),
+                pfn_create_node_resources: Some(create_node_resources),
+                pfn_destroy_node_resources: Some(destroy_node_resources),
+                pfn_record_debug_marker: Some(record_debug_marker),
+                pfn_set_debug_name: Some(set_debug_name)
+            }
+        }
+    }
+
+    // The runtime owns one reference through its user context and releases it in pfn_destroy_runtime. That may already have happened when creation
+    // fails, so the reference is only reclaimed here if the runtime provably still holds it.
+    pub unsafe fn create_device(self, device_create_info: *const DeviceCreateInfo) -> RpsResult<crate::Device> {
+        let user_context = Arc::into_raw(self.backend.clone());
+        let runtime_create_info = self.create_info(user_context);
+        let create_info = NullRuntimeDeviceCreateInfo {
+            device_create_info,
+            runtime_create_info: &runtime_create_info
+        };
+
+        let result = null_runtime_device_create(&create_info);
+        if result.is_err() && Arc::strong_count(&self.backend) > 1 {
+            drop(Arc::from_raw(user_context));
+        }
+        result
+    }
+}
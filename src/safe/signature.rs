@@ -0,0 +1,160 @@
+use std::{
+    ffi::{c_char, CStr},
+    slice
+};
+
+use crate::{
+    rpsl_entry_get_signature_desc, safe::RpsArg, AccessAttr, ErrorContext, NodeDeclFlags, NodeDesc, ParamAttr, ParameterDesc, ParameterFlags, ProgramCreateInfo, RenderGraphSignatureDesc,
+    RpsError, RpsResult, RpslEntry, SemanticAttr, TypeInfo
+};
+
+#[inline]
+unsafe fn c_str_to_string(name: *const c_char) -> String {
+    if name.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    }
+}
+
+#[inline]
+unsafe fn raw_slice<'a, T>(data: *const T, len: u32) -> &'a [T] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len as _)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParamInfo {
+    pub name: String,
+    pub type_info: TypeInfo,
+    pub array_size: u32,
+    pub flags: ParameterFlags,
+    pub attr: ParamAttr
+}
+
+impl ParamInfo {
+    pub unsafe fn from_desc(param_desc: &ParameterDesc) -> Self {
+        Self {
+            name: c_str_to_string(param_desc.name),
+            type_info: param_desc.type_info,
+            array_size: param_desc.array_size,
+            flags: param_desc.flags,
+            attr: param_desc.attr.cast::<ParamAttr>().as_ref().copied().unwrap_or_default()
+        }
+    }
+
+    #[inline]
+    pub fn access(&self) -> AccessAttr {
+        self.attr.access
+    }
+
+    #[inline]
+    pub fn semantic(&self) -> SemanticAttr {
+        self.attr.semantic
+    }
+
+    #[inline]
+    pub fn is_resource(&self) -> bool {
+        self.flags.contains(ParameterFlags::RESOURCE)
+    }
+
+    #[inline]
+    pub fn is_output(&self) -> bool {
+        self.flags.contains(ParameterFlags::OUT)
+    }
+
+    #[inline]
+    pub fn is_optional(&self) -> bool {
+        self.flags.contains(ParameterFlags::OPTIONAL)
+    }
+
+    #[inline]
+    pub fn accepts<T: RpsArg>(&self) -> bool {
+        self.array_size == T::ARRAY_SIZE && T::matches_element(self.type_info, self.flags)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NodeInfo {
+    pub name: String,
+    pub flags: NodeDeclFlags,
+    pub params: Vec<ParamInfo>
+}
+
+impl NodeInfo {
+    pub unsafe fn from_desc(node_desc: &NodeDesc) -> Self {
+        Self {
+            name: c_str_to_string(node_desc.name),
+            flags: node_desc.flags,
+            params: raw_slice(node_desc.param_descs, node_desc.num_params)
+                .iter()
+                .map(|param_desc| ParamInfo::from_desc(param_desc))
+                .collect()
+        }
+    }
+
+    #[inline]
+    pub fn param(&self, name: &str) -> Option<&ParamInfo> {
+        self.params.iter().find(|param| param.name == name)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EntrySignature {
+    pub name: String,
+    pub max_external_resources: u32,
+    pub params: Vec<ParamInfo>,
+    pub nodes: Vec<NodeInfo>
+}
+
+impl EntrySignature {
+    #[inline]
+    pub fn new(rpsl_entry: RpslEntry) -> RpsResult<Self> {
+        unsafe { Ok(Self::from_desc(&rpsl_entry_get_signature_desc(rpsl_entry)?)) }
+    }
+
+    pub unsafe fn from_create_info(create_info: &ProgramCreateInfo) -> RpsResult<Self> {
+        if let Some(signature_desc) = create_info.signature_desc.as_ref() {
+            Ok(Self::from_desc(signature_desc))
+        } else if create_info.rpsl_entry_point == RpslEntry::null() {
+            Ok(Self::default())
+        } else {
+            Self::new(create_info.rpsl_entry_point)
+        }
+    }
+
+    pub unsafe fn from_desc(signature_desc: &RenderGraphSignatureDesc) -> Self {
+        Self {
+            name: c_str_to_string(signature_desc.name),
+            max_external_resources: signature_desc.max_external_resources,
+            params: raw_slice(signature_desc.param_descs, signature_desc.num_params)
+                .iter()
+                .map(|param_desc| ParamInfo::from_desc(param_desc))
+                .collect(),
+            nodes: raw_slice(signature_desc.node_descs, signature_desc.num_node_descs)
+                .iter()
+                .map(|node_desc| NodeInfo::from_desc(node_desc))
+                .collect()
+        }
+    }
+
+    #[inline]
+    pub fn param(&self, name: &str) -> Option<&ParamInfo> {
+        self.params.iter().find(|param| param.name == name)
+    }
+
+    #[inline]
+    pub fn node(&self, name: &str) -> Option<&NodeInfo> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+
+    pub fn validate_node_names<'n>(&self, names: impl IntoIterator<Item = &'n str>) -> RpsResult<()> {
+        match names.into_iter().find(|name| self.node(name).is_none()) {
+            Some(name) => Err(RpsError::UnknownNode(ErrorContext::new("EntrySignature::validate_node_names")).with_name(name)),
+            None => Ok(())
+        }
+    }
+}
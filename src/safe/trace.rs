@@ -0,0 +1,517 @@
+use std::{
+    env, fmt, fs, io,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError}
+};
+
+use crate::{
+    safe::{CmdContext, CreateHeapArgs, CreateNodeResourcesArgs, CreateResourceArgs, DebugMarkerArgs, Program, RuntimeBackend},
+    CmdCallback, CmdCallbackContext, CmdCallbackFlags, ErrorContext, ResourceType, Result, RpsError, RpsResult, RuntimeDebugMarkerMode, RuntimeHeap, RuntimeResource
+};
+
+pub const UPDATE_GOLDEN_ENV: &str = "RPS_UPDATE_GOLDEN";
+
+const RESOURCE_TYPE_NAMES: &[&str] = &["buffer", "image_1d", "image_2d", "image_3d", "unknown"];
+const DEBUG_MARKER_MODE_NAMES: &[&str] = &["begin", "label", "end", "unknown"];
+
+fn resource_type_name(resource_type: ResourceType) -> &'static str {
+    match resource_type {
+        ResourceType::BUFFER => "buffer",
+        ResourceType::IMAGE_1D => "image_1d",
+        ResourceType::IMAGE_2D => "image_2d",
+        ResourceType::IMAGE_3D => "image_3d",
+        _ => "unknown"
+    }
+}
+
+fn debug_marker_mode_name(mode: RuntimeDebugMarkerMode) -> &'static str {
+    match mode {
+        RuntimeDebugMarkerMode::BEGIN => "begin",
+        RuntimeDebugMarkerMode::LABEL => "label",
+        RuntimeDebugMarkerMode::END => "end",
+        _ => "unknown"
+    }
+}
+
+#[inline]
+pub fn is_update_golden_value(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on")
+}
+
+#[inline]
+pub fn update_golden_requested() -> bool {
+    env::var(UPDATE_GOLDEN_ENV).is_ok_and(|value| is_update_golden_value(&value))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    CreateHeap {
+        heap: u64,
+        memory_type_index: u32,
+        size: usize,
+        alignment: usize,
+        debug_name: Option<String>
+    },
+    DestroyHeap {
+        heap: u64
+    },
+    CreateResource {
+        resource: u64,
+        resource_id: u32,
+        resource_type: &'static str,
+        temporal_layers: u32,
+        size: u64,
+        alignment: u32
+    },
+    DestroyResource {
+        resource: u64,
+        resource_type: &'static str
+    },
+    CreateNodeResources {
+        node_tag: u32,
+        num_args: usize
+    },
+    DestroyNodeResources,
+    DebugMarker {
+        mode: &'static str,
+        text: Option<String>
+    },
+    SetDebugName {
+        resource: u64,
+        resource_type: &'static str,
+        name: String
+    },
+    NodeCallback {
+        name: String,
+        user_tag: u32,
+        num_args: u32
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateHeap {
+                heap,
+                memory_type_index,
+                size,
+                alignment,
+                debug_name
+            } => {
+                write!(f, "create_heap heap={heap} memory_type_index={memory_type_index} size={size} alignment={alignment}")?;
+                if let Some(debug_name) = debug_name {
+                    write!(f, " debug_name={debug_name:?}")?;
+                }
+                Ok(())
+            }
+            Self::DestroyHeap { heap } => write!(f, "destroy_heap heap={heap}"),
+            Self::CreateResource {
+                resource,
+                resource_id,
+                resource_type,
+                temporal_layers,
+                size,
+                alignment
+            } => {
+                write!(
+                    f,
+                    "create_resource resource={resource} resource_id={resource_id} type={resource_type} temporal_layers={temporal_layers} size={size} alignment={alignment}"
+                )
+            }
+            Self::DestroyResource { resource, resource_type } => write!(f, "destroy_resource resource={resource} type={resource_type}"),
+            Self::CreateNodeResources { node_tag, num_args } => write!(f, "create_node_resources node_tag={node_tag} num_args={num_args}"),
+            Self::DestroyNodeResources => write!(f, "destroy_node_resources"),
+            Self::DebugMarker { mode, text } => {
+                write!(f, "debug_marker mode={mode}")?;
+                if let Some(text) = text {
+                    write!(f, " text={text:?}")?;
+                }
+                Ok(())
+            }
+            Self::SetDebugName { resource, resource_type, name } => write!(f, "set_debug_name resource={resource} type={resource_type} name={name:?}"),
+            Self::NodeCallback { name, user_tag, num_args } => write!(f, "node_callback name={name:?} user_tag={user_tag} num_args={num_args}")
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub events: Vec<TraceEvent>
+}
+
+impl Trace {
+    #[inline]
+    pub fn to_text(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn matches_golden(&self, path: impl AsRef<Path>) -> io::Result<bool> {
+        Ok(fs::read_to_string(path)?.replace("\r\n", "\n") == self.to_text())
+    }
+
+    #[inline]
+    pub fn assert_golden(&self, path: impl AsRef<Path>) {
+        self.assert_or_update_golden(path, update_golden_requested());
+    }
+
+    pub fn assert_or_update_golden(&self, path: impl AsRef<Path>, update: bool) {
+        let path = path.as_ref();
+        let actual = self.to_text();
+
+        if update {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).unwrap_or_else(|error| panic!("failed to create {}: {error}", parent.display()));
+            }
+            fs::write(path, &actual).unwrap_or_else(|error| panic!("failed to write golden file {}: {error}", path.display()));
+            return;
+        }
+
+        let expected = fs::read_to_string(path)
+            .unwrap_or_else(|error| panic!("failed to read golden file {} ({error}), run with {UPDATE_GOLDEN_ENV}=1 to create it", path.display()))
+            .replace("\r\n", "\n");
+        if expected == actual {
+            return;
+        }
+
+        let (line, expected_line, actual_line) = expected
+            .lines()
+            .map(Some)
+            .chain(std::iter::repeat(None))
+            .zip(actual.lines().map(Some).chain(std::iter::repeat(None)))
+            .enumerate()
+            .find(|(_, (expected, actual))| expected != actual)
+            .map(|(index, (expected, actual))| (index + 1, expected.unwrap_or("<end of trace>"), actual.unwrap_or("<end of trace>")))
+            .unwrap_or((0, "", ""));
+
+        panic!(
+            "trace does not match golden file {} at line {line}\n  expected: {expected_line}\n    actual: {actual_line}\nrun with {UPDATE_GOLDEN_ENV}=1 to update it",
+            path.display()
+        );
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.events.iter().try_for_each(|event| writeln!(f, "{event}"))
+    }
+}
+
+fn unquote(value: &str) -> Option<(String, &str)> {
+    let mut unquoted = String::new();
+    let mut chars = value.strip_prefix('"')?.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((unquoted, &value[index + 2..])),
+            '\\' => {
+                unquoted.push(match chars.next()?.1 {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    'u' => {
+                        let digits = chars.by_ref().skip(1).take_while(|(_, c)| *c != '}').map(|(_, c)| c).collect::<String>();
+                        char::from_u32(u32::from_str_radix(&digits, 16).ok()?)?
+                    }
+                    escaped => escaped
+                })
+            }
+            c => unquoted.push(c)
+        }
+    }
+    None
+}
+
+struct TraceLine<'s> {
+    kind: &'s str,
+    fields: Vec<(&'s str, String)>
+}
+
+impl<'s> TraceLine<'s> {
+    fn parse(line: &'s str) -> Option<Self> {
+        let (kind, mut rest) = line.split_once(' ').unwrap_or((line, ""));
+        let mut fields = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                return Some(Self { kind, fields });
+            }
+
+            let (key, value) = rest.split_once('=')?;
+            let (value, remainder) = if value.starts_with('"') {
+                unquote(value)?
+            } else {
+                let (value, remainder) = value.split_once(' ').unwrap_or((value, ""));
+                (value.to_owned(), remainder)
+            };
+            fields.push((key, value));
+            rest = remainder;
+        }
+    }
+
+    fn take_str(&mut self, key: &str) -> Option<String> {
+        let index = self.fields.iter().position(|(field, _)| *field == key)?;
+        Some(self.fields.remove(index).1)
+    }
+
+    fn take<T: FromStr>(&mut self, key: &str) -> Option<T> {
+        self.take_str(key)?.parse().ok()
+    }
+
+    fn take_name(&mut self, key: &str, names: &[&'static str]) -> Option<&'static str> {
+        let value = self.take_str(key)?;
+        names.iter().copied().find(|name| *name == value)
+    }
+
+    fn event(&mut self) -> Option<TraceEvent> {
+        let event = match self.kind {
+            "create_heap" => {
+                TraceEvent::CreateHeap {
+                    heap: self.take("heap")?,
+                    memory_type_index: self.take("memory_type_index")?,
+                    size: self.take("size")?,
+                    alignment: self.take("alignment")?,
+                    debug_name: self.take_str("debug_name")
+                }
+            }
+            "destroy_heap" => TraceEvent::DestroyHeap { heap: self.take("heap")? },
+            "create_resource" => {
+                TraceEvent::CreateResource {
+                    resource: self.take("resource")?,
+                    resource_id: self.take("resource_id")?,
+                    resource_type: self.take_name("type", RESOURCE_TYPE_NAMES)?,
+                    temporal_layers: self.take("temporal_layers")?,
+                    size: self.take("size")?,
+                    alignment: self.take("alignment")?
+                }
+            }
+            "destroy_resource" => {
+                TraceEvent::DestroyResource {
+                    resource: self.take("resource")?,
+                    resource_type: self.take_name("type", RESOURCE_TYPE_NAMES)?
+                }
+            }
+            "create_node_resources" => {
+                TraceEvent::CreateNodeResources {
+                    node_tag: self.take("node_tag")?,
+                    num_args: self.take("num_args")?
+                }
+            }
+            "destroy_node_resources" => TraceEvent::DestroyNodeResources,
+            "debug_marker" => {
+                TraceEvent::DebugMarker {
+                    mode: self.take_name("mode", DEBUG_MARKER_MODE_NAMES)?,
+                    text: self.take_str("text")
+                }
+            }
+            "set_debug_name" => {
+                TraceEvent::SetDebugName {
+                    resource: self.take("resource")?,
+                    resource_type: self.take_name("type", RESOURCE_TYPE_NAMES)?,
+                    name: self.take_str("name")?
+                }
+            }
+            "node_callback" => {
+                TraceEvent::NodeCallback {
+                    name: self.take_str("name")?,
+                    user_tag: self.take("user_tag")?,
+                    num_args: self.take("num_args")?
+                }
+            }
+            _ => return None
+        };
+
+        self.fields.is_empty().then_some(event)
+    }
+}
+
+impl FromStr for TraceEvent {
+    type Err = RpsError;
+
+    fn from_str(line: &str) -> RpsResult<Self> {
+        TraceLine::parse(line)
+            .and_then(|mut line| line.event())
+            .ok_or_else(|| RpsError::InvalidData(ErrorContext::new("TraceEvent::from_str")).with_name(line))
+    }
+}
+
+impl FromStr for Trace {
+    type Err = RpsError;
+
+    fn from_str(text: &str) -> RpsResult<Self> {
+        Ok(Self {
+            events: text.lines().filter(|line| !line.trim().is_empty()).map(str::parse).collect::<RpsResult<_>>()?
+        })
+    }
+}
+
+type SharedEvents = Mutex<Vec<TraceEvent>>;
+
+fn node_event(context: &CmdContext<'_>) -> TraceEvent {
+    TraceEvent::NodeCallback {
+        name: context.node_name().unwrap_or_default().to_owned(),
+        user_tag: context.user_tag(),
+        num_args: context.num_args()
+    }
+}
+
+unsafe extern "C" fn trace_node_callback(context: *const CmdCallbackContext) {
+    let events = &*(*context).cmd_callback_context.cast::<SharedEvents>();
+    events.lock().unwrap_or_else(PoisonError::into_inner).push(node_event(&CmdContext::from_raw(context)));
+}
+
+#[derive(Clone, Default)]
+pub struct TraceRecorder {
+    events: Arc<SharedEvents>
+}
+
+impl TraceRecorder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn events(&self) -> MutexGuard<'_, Vec<TraceEvent>> {
+        self.events.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    pub fn backend(&self) -> TraceBackend {
+        TraceBackend {
+            recorder: self.clone(),
+            next_handle: 1
+        }
+    }
+
+    #[inline]
+    pub fn default_node_callback(&self) -> CmdCallback {
+        CmdCallback {
+            pfn_callback: Some(trace_node_callback),
+            user_context: Arc::as_ptr(&self.events).cast_mut().cast(),
+            flags: CmdCallbackFlags::NONE
+        }
+    }
+
+    // Wraps a node callback so bound and dynamic nodes are traced the same way as nodes that fall back to default_node_callback.
+    pub fn traced<F, E>(&self, mut callback: F) -> impl FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static
+    where
+        F: FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static,
+        E: Into<Result>
+    {
+        let recorder = self.clone();
+        move |context| {
+            recorder.record(node_event(context));
+            callback(context)
+        }
+    }
+
+    #[inline]
+    pub fn bind_node<F, E>(&self, program: &mut Program<'_>, name: &str, callback: F) -> RpsResult<()>
+    where
+        F: FnMut(&mut CmdContext<'_>) -> std::result::Result<(), E> + Send + 'static,
+        E: Into<Result>
+    {
+        program.bind_node(name, self.traced(callback))
+    }
+
+    #[inline]
+    pub fn record(&self, event: TraceEvent) {
+        self.events().push(event);
+    }
+
+    #[inline]
+    pub fn trace(&self) -> Trace {
+        Trace { events: self.events().clone() }
+    }
+
+    #[inline]
+    pub fn take(&self) -> Trace {
+        Trace {
+            events: std::mem::take(&mut *self.events())
+        }
+    }
+}
+
+pub struct TraceBackend {
+    recorder: TraceRecorder,
+    next_handle: u64
+}
+
+impl TraceBackend {
+    #[inline]
+    fn allocate_handle(&mut self) -> u64 {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        handle
+    }
+}
+
+impl RuntimeBackend for TraceBackend {
+    fn create_heap(&mut self, args: &CreateHeapArgs<'_>) -> RpsResult<RuntimeHeap> {
+        let heap = self.allocate_handle();
+        self.recorder.record(TraceEvent::CreateHeap {
+            heap,
+            memory_type_index: args.memory_type_index,
+            size: args.size,
+            alignment: args.alignment,
+            debug_name: args.debug_name.map(str::to_owned)
+        });
+        Ok(RuntimeHeap::from_raw(heap as _))
+    }
+
+    fn destroy_heaps(&mut self, heaps: &[RuntimeHeap]) {
+        for heap in heaps {
+            self.recorder.record(TraceEvent::DestroyHeap { heap: heap.into_raw() as _ });
+        }
+    }
+
+    fn create_resource(&mut self, args: &CreateResourceArgs<'_>) -> RpsResult<RuntimeResource> {
+        let resource = self.allocate_handle();
+        self.recorder.record(TraceEvent::CreateResource {
+            resource,
+            resource_id: args.resource_id,
+            resource_type: resource_type_name(args.desc.type_),
+            temporal_layers: args.desc.temporal_layers,
+            size: args.alloc_requirement.size,
+            alignment: args.alloc_requirement.alignment
+        });
+        Ok(RuntimeResource::from_raw(resource as _))
+    }
+
+    fn destroy_resources(&mut self, resource_type: ResourceType, resources: &[RuntimeResource]) {
+        for resource in resources {
+            self.recorder.record(TraceEvent::DestroyResource {
+                resource: resource.into_raw() as _,
+                resource_type: resource_type_name(resource_type)
+            });
+        }
+    }
+
+    fn create_node_resources(&mut self, args: &CreateNodeResourcesArgs<'_>) -> RpsResult<()> {
+        self.recorder.record(TraceEvent::CreateNodeResources {
+            node_tag: args.node_tag,
+            num_args: args.args.len()
+        });
+        Ok(())
+    }
+
+    fn destroy_node_resources(&mut self) {
+        self.recorder.record(TraceEvent::DestroyNodeResources);
+    }
+
+    fn record_debug_marker(&mut self, args: &DebugMarkerArgs<'_>) {
+        self.recorder.record(TraceEvent::DebugMarker {
+            mode: debug_marker_mode_name(args.mode),
+            text: args.text.map(str::to_owned)
+        });
+    }
+
+    fn set_debug_name(&mut self, resource: RuntimeResource, resource_type: ResourceType, name: &str) {
+        self.recorder.record(TraceEvent::SetDebugName {
+            resource: resource.into_raw() as _,
+            resource_type: resource_type_name(resource_type),
+            name: name.to_owned()
+        });
+    }
+}
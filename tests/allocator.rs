@@ -0,0 +1,144 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    ffi::c_void,
+    ptr, slice
+};
+
+use rps::{
+    safe::{AllocationStats, CountingAllocator, Device, HostAllocator},
+    Allocator
+};
+
+unsafe fn alloc(allocator: &Allocator, size: usize, alignment: usize) -> *mut u8 {
+    allocator.pfn_alloc.unwrap()(allocator.context, size, alignment).cast()
+}
+
+unsafe fn realloc(allocator: &Allocator, buffer: *mut u8, old_size: usize, new_size: usize, alignment: usize) -> *mut u8 {
+    allocator.pfn_realloc.unwrap()(allocator.context, buffer.cast::<c_void>(), old_size, new_size, alignment).cast()
+}
+
+unsafe fn free(allocator: &Allocator, buffer: *mut u8) {
+    allocator.pfn_free.unwrap()(allocator.context, buffer.cast::<c_void>())
+}
+
+#[test]
+fn allocations_honor_alignment() {
+    let host_allocator = HostAllocator::default();
+    let allocator = host_allocator.allocator();
+
+    let buffers = [1, 2, 8, 16, 64, 256, 4096]
+        .into_iter()
+        .map(|alignment| {
+            let buffer = unsafe { alloc(&allocator, 24, alignment) };
+            assert!(!buffer.is_null());
+            assert_eq!(buffer as usize % alignment, 0, "alignment {alignment}");
+            unsafe { ptr::write_bytes(buffer, 0xab, 24) };
+            buffer
+        })
+        .collect::<Vec<_>>();
+
+    let stats = host_allocator.stats();
+    assert_eq!(stats.live_allocations, buffers.len());
+    assert!(stats.live_bytes >= 24 * buffers.len());
+    assert_eq!(stats.peak_bytes, stats.live_bytes);
+
+    for buffer in buffers {
+        unsafe { free(&allocator, buffer) };
+    }
+
+    let stats = host_allocator.stats();
+    assert_eq!((stats.live_bytes, stats.live_allocations), (0, 0));
+    assert!(stats.peak_bytes >= 24 * 7);
+}
+
+#[test]
+fn realloc_preserves_contents_and_alignment() {
+    let host_allocator = HostAllocator::default();
+    let allocator = host_allocator.allocator();
+
+    unsafe {
+        let buffer = alloc(&allocator, 16, 16);
+        for (index, byte) in slice::from_raw_parts_mut(buffer, 16).iter_mut().enumerate() {
+            *byte = index as u8;
+        }
+
+        let grown = realloc(&allocator, buffer, 16, 1024, 128);
+        assert_eq!(grown as usize % 128, 0);
+        assert_eq!(slice::from_raw_parts(grown, 16), (0..16).collect::<Vec<u8>>());
+        assert_eq!(host_allocator.stats().live_allocations, 1);
+        assert!(host_allocator.stats().live_bytes >= 1024);
+
+        let shrunk = realloc(&allocator, grown, 1024, 4, 4);
+        assert_eq!(slice::from_raw_parts(shrunk, 4), [0, 1, 2, 3]);
+        let stats = host_allocator.stats();
+        assert_eq!(stats.live_allocations, 1);
+        assert!(stats.live_bytes < 1024);
+        assert!(stats.peak_bytes >= 1024 + 16);
+
+        free(&allocator, shrunk);
+    }
+
+    assert_eq!(host_allocator.stats().live_bytes, 0);
+}
+
+#[test]
+fn null_buffers_are_handled() {
+    let host_allocator = HostAllocator::default();
+    let allocator = host_allocator.allocator();
+
+    unsafe {
+        free(&allocator, ptr::null_mut());
+        assert_eq!(host_allocator.stats(), AllocationStats::default());
+
+        let buffer = realloc(&allocator, ptr::null_mut(), 0, 32, 8);
+        assert!(!buffer.is_null());
+        assert_eq!(host_allocator.stats().live_allocations, 1);
+        free(&allocator, buffer);
+    }
+
+    assert_eq!(host_allocator.stats().live_allocations, 0);
+}
+
+#[test]
+fn counting_allocator_tracks_peak_usage() {
+    let allocator = CountingAllocator::new(System);
+    let small = Layout::from_size_align(64, 8).unwrap();
+    let large = Layout::from_size_align(512, 64).unwrap();
+
+    unsafe {
+        let first = allocator.alloc(small);
+        let second = allocator.alloc(large);
+        assert_eq!(
+            allocator.stats(),
+            AllocationStats {
+                live_bytes: 576,
+                peak_bytes: 576,
+                live_allocations: 2
+            }
+        );
+
+        allocator.dealloc(second, large);
+        allocator.dealloc(first, small);
+    }
+
+    assert_eq!(
+        allocator.stats(),
+        AllocationStats {
+            live_bytes: 0,
+            peak_bytes: 576,
+            live_allocations: 0
+        }
+    );
+}
+
+#[test]
+fn device_reports_allocation_stats() {
+    let device = Device::builder().host_allocator(HostAllocator::default()).build().unwrap();
+    let stats = device.allocation_stats().unwrap();
+    assert!(stats.live_allocations > 0);
+    assert!(stats.live_bytes > 0);
+    assert!(stats.peak_bytes >= stats.live_bytes);
+
+    let device = Device::builder().build().unwrap();
+    assert_eq!(device.allocation_stats(), None);
+}
@@ -0,0 +1,90 @@
+use rps::{safe::RpsArg, ImageView, ParameterDesc, ParameterFlags, TypeId, TypeInfo};
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Exposure {
+    value: f32
+}
+
+unsafe impl RpsArg for Exposure {
+    const TYPE_INFO: TypeInfo = TypeInfo {
+        size: 4,
+        id: TypeId::USER_DEFINED_BEGIN.into_raw() as _
+    };
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct LightIndex {
+    index: u32
+}
+
+unsafe impl RpsArg for LightIndex {
+    const TYPE_INFO: TypeInfo = TypeInfo {
+        size: 4,
+        id: TypeId::USER_DEFINED_BEGIN.into_raw() as u16 + 1
+    };
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Untyped {
+    bits: u32
+}
+
+unsafe impl RpsArg for Untyped {}
+
+fn opaque(size: u16) -> TypeInfo {
+    TypeInfo {
+        size,
+        id: TypeId::OPAQUE.into_raw() as _
+    }
+}
+
+#[test]
+fn user_types_match_only_their_own_id() {
+    let flags = ParameterFlags::empty();
+
+    assert!(Exposure::matches_element(Exposure::TYPE_INFO, flags));
+    assert!(LightIndex::matches_element(LightIndex::TYPE_INFO, flags));
+
+    assert!(!Exposure::matches_element(LightIndex::TYPE_INFO, flags));
+    assert!(!LightIndex::matches_element(Exposure::TYPE_INFO, flags));
+    assert!(!Untyped::matches_element(Exposure::TYPE_INFO, flags));
+}
+
+#[test]
+fn opaque_params_match_by_size() {
+    let flags = ParameterFlags::empty();
+
+    assert!(Exposure::matches_element(opaque(4), flags));
+    assert!(Untyped::matches_element(opaque(4), flags));
+    assert!(!Exposure::matches_element(opaque(8), flags));
+    assert!(!Untyped::matches_element(opaque(4), ParameterFlags::RESOURCE));
+}
+
+#[test]
+fn built_in_types_check_id_and_resource_flag() {
+    let u32_info = <u32 as RpsArg>::TYPE_INFO;
+
+    assert!(u32::matches_element(u32_info, ParameterFlags::empty()));
+    assert!(!i32::matches_element(u32_info, ParameterFlags::empty()));
+    assert!(!f32::matches_element(u32_info, ParameterFlags::empty()));
+    assert!(!u32::matches_element(Exposure::TYPE_INFO, ParameterFlags::empty()));
+    assert!(!ImageView::matches_element(ImageView::TYPE_INFO, ParameterFlags::empty()));
+    assert!(ImageView::matches_element(ImageView::TYPE_INFO, ParameterFlags::RESOURCE));
+}
+
+#[test]
+fn arrays_check_their_size() {
+    let param_desc = ParameterDesc {
+        type_info: LightIndex::TYPE_INFO,
+        array_size: 4,
+        ..Default::default()
+    };
+
+    assert!(<[LightIndex; 4]>::matches(&param_desc));
+    assert!(!<[LightIndex; 3]>::matches(&param_desc));
+    assert!(!<[Exposure; 4]>::matches(&param_desc));
+    assert!(!LightIndex::matches(&param_desc));
+}
@@ -0,0 +1,161 @@
+use std::ptr;
+
+use rps::{
+    safe::{CpuBackend, CpuExecutor, CreateResourceArgs, Device, FrameUpdate, GraphBuilder, NodeArg, NodeParam, RenderGraph, RuntimeBackend},
+    AccessAttr, AccessFlags, ClearValue, GpuMemoryRequirement, HeapId, HeapPlacement, NodeDeclFlags, RenderGraphSignatureDesc, ResourceBufferDesc, ResourceBufferImageDesc, ResourceDesc,
+    ResourceId, ResourceType, RpsError, RpsResult, RuntimeCommandBuffer, RuntimeResource, ScheduleFlags, ShaderStage, INDEX_NONE_U32
+};
+
+fn buffer_desc(size: u32) -> ResourceDesc {
+    ResourceDesc {
+        type_: ResourceType::BUFFER,
+        temporal_layers: 1,
+        buffer_image: ResourceBufferImageDesc {
+            buffer: ResourceBufferDesc {
+                size_in_bytes_lo: size,
+                size_in_bytes_hi: 0
+            }
+        },
+        ..Default::default()
+    }
+}
+
+fn place(backend: &mut CpuBackend, resource_id: ResourceId, heap_id: HeapId, size: u32) -> RpsResult<RuntimeResource> {
+    backend.create_resource(&CreateResourceArgs {
+        resource_id,
+        desc: &buffer_desc(size),
+        original_desc: ptr::null_mut(),
+        clear_value: &ClearValue::default(),
+        alloc_requirement: GpuMemoryRequirement {
+            size: size.into(),
+            alignment: 16,
+            memory_type_index: 0
+        },
+        alloc_placement: HeapPlacement { heap_id, offset: 0 },
+        all_accesses: AccessAttr::default(),
+        initial_access: AccessAttr::default(),
+        mutable_formats: &[],
+        buffer_formatted_write: false,
+        buffer_formatted_read: false
+    })
+}
+
+#[test]
+fn resource_ids_are_scoped_per_render_graph() {
+    let executor = CpuExecutor::new();
+    let mut backend = executor.backend();
+    let first = rps::RenderGraph::from_raw(1 as _);
+    let second = rps::RenderGraph::from_raw(2 as _);
+
+    executor.with_render_graph(first, || place(&mut backend, 0, INDEX_NONE_U32, 96)).unwrap();
+    let resource = executor.with_render_graph(second, || place(&mut backend, 0, INDEX_NONE_U32, 32)).unwrap();
+    assert_eq!(executor.num_heaps(), 0);
+    assert_eq!(executor.resource_data(first, 0).map(|data| data.len()), Some(96));
+    assert_eq!(executor.resource_data(second, 0).map(|data| data.len()), Some(32));
+
+    backend.destroy_resources(ResourceType::BUFFER, &[resource]);
+    assert_eq!(executor.resource_data(first, 0).map(|data| data.len()), Some(96));
+    assert_eq!(executor.resource_data(second, 0), None);
+}
+
+#[test]
+fn placements_need_a_render_graph_to_resolve_heap_ids() {
+    let executor = CpuExecutor::new();
+    let mut backend = executor.backend();
+
+    assert!(matches!(place(&mut backend, 0, 0, 64), Err(RpsError::IndexOutOfBounds(_))));
+    assert_eq!(executor.num_resources(), 0);
+}
+
+fn uav() -> AccessAttr {
+    AccessAttr {
+        access_flags: AccessFlags::UNORDERED_ACCESS,
+        access_stages: ShaderStage::CS
+    }
+}
+
+fn srv() -> AccessAttr {
+    AccessAttr {
+        access_flags: AccessFlags::SHADER_RESOURCE,
+        access_stages: ShaderStage::CS
+    }
+}
+
+// Builds fill(a) -> copy(a, b) -> fill(c) -> check(b, c). `a` is dead once copied, so RPS may alias it with `c`, but `b` and `c` are live together.
+fn build_copy_graph(executor: &CpuExecutor, builder: &mut GraphBuilder<'_>, seed: u8) -> RpsResult<[ResourceId; 3]> {
+    let fill = builder.declare_node("fill", NodeDeclFlags::COMPUTE, &[NodeParam::buffer("dst", uav()), NodeParam::value::<u8>("value")])?;
+    let copy = builder.declare_node("copy", NodeDeclFlags::COMPUTE, &[NodeParam::buffer("src", srv()), NodeParam::buffer("dst", uav())])?;
+    let check = builder.declare_node("check", NodeDeclFlags::COMPUTE, &[NodeParam::buffer("copied", srv()), NodeParam::buffer("filled", srv())])?;
+
+    let a = builder.declare_resource("a", &buffer_desc(256))?;
+    let b = builder.declare_resource("b", &buffer_desc(256))?;
+    let c = builder.declare_resource("c", &buffer_desc(256))?;
+    let first_value = builder.alloc_value(seed)?;
+    let second_value = builder.alloc_value(seed.wrapping_add(1))?;
+
+    let fill_node = || {
+        executor.node(|context| {
+            let value = *context.cmd().arg::<u8>(1)?;
+            context.resource(0)?.fill(value);
+            Ok(())
+        })
+    };
+
+    builder.add_node(fill, &[NodeArg::buffer(a.buffer_view()), NodeArg::value(first_value)], fill_node())?;
+    builder.add_node(
+        copy,
+        &[NodeArg::buffer(a.buffer_view()), NodeArg::buffer(b.buffer_view())],
+        executor.node(|context| {
+            let [src, dst] = <[_; 2]>::try_from(context.resources(&[0, 1])?).unwrap();
+            dst.copy_from_slice(src);
+            Ok(())
+        })
+    )?;
+    builder.add_node(fill, &[NodeArg::buffer(c.buffer_view()), NodeArg::value(second_value)], fill_node())?;
+    builder.add_node(
+        check,
+        &[NodeArg::buffer(b.buffer_view()), NodeArg::buffer(c.buffer_view())],
+        executor.node(|context| {
+            let resources = context.resources(&[0, 1])?;
+            assert!(resources[0].iter().all(|&byte| byte != resources[1][0]));
+            Ok(())
+        })
+    )?;
+
+    Ok([a.id(), b.id(), c.id()])
+}
+
+#[test]
+fn recorded_graphs_write_resources_and_keep_live_resources_apart() {
+    let executor = CpuExecutor::new();
+    let device = Device::builder().build_with_backend(executor.backend()).unwrap();
+    let schedule_flags = ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION | ScheduleFlags::KEEP_PROGRAM_ORDER;
+
+    let mut render_graphs = Vec::new();
+    for seed in [0x10u8, 0x40] {
+        let mut render_graph = RenderGraph::builder()
+            .schedule_flags(schedule_flags)
+            .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+            .unwrap();
+
+        let mut resource_ids = None;
+        executor
+            .update_with(&mut render_graph, &FrameUpdate::new(0).schedule_flags(schedule_flags), |builder| {
+                resource_ids = Some(build_copy_graph(&executor, builder, seed)?);
+                Ok(())
+            })
+            .unwrap();
+        unsafe { render_graph.record_all_commands(RuntimeCommandBuffer::null(), 0) }.unwrap();
+
+        render_graphs.push((render_graph, resource_ids.unwrap(), seed));
+    }
+
+    for (render_graph, [a, b, c], seed) in &render_graphs {
+        let handle = render_graph.handle();
+        assert_eq!(executor.resource_data(handle, *b), Some(vec![*seed; 256]));
+        assert_eq!(executor.resource_data(handle, *c), Some(vec![seed + 1; 256]));
+
+        let aliased = executor.aliased_resources(handle);
+        assert!(aliased.iter().all(|&pair| pair == ((*a).min(*c), (*a).max(*c))), "{aliased:?}");
+    }
+}
@@ -0,0 +1,230 @@
+use std::ptr;
+
+use rps::{
+    null_runtime_device_create,
+    safe::{CmdContext, CmdDiagnostic, Device, DiagnosticsSnapshot, FrameUpdate, GraphBuilder, NodeArg, NodeDiagnostic, NodeParam, NodeResourceAccess, RenderGraph, ResourceDiagnostic},
+    AccessAttr, AccessFlags, ClearValue, CommandBatch, GpuMemoryRequirement, HeapPlacement, NodeDeclFlags, NullRuntimeDeviceCreateInfo, RenderGraphDiagnosticInfoFlags,
+    RenderGraphSignatureDesc, ResourceBufferDesc, ResourceBufferImageDesc, ResourceDesc, ResourceType, RpsResult, RuntimeResource, ScheduleFlags, ShaderStage, SubresourceRange
+};
+
+fn access(access_flags: AccessFlags) -> AccessAttr {
+    AccessAttr { access_flags, ..Default::default() }
+}
+
+fn label(access_flags: AccessFlags) -> String {
+    access_flags.iter_names().map(|(name, _)| name).collect::<Vec<_>>().join(" | ")
+}
+
+fn resource(name: &str) -> ResourceDiagnostic {
+    ResourceDiagnostic {
+        name: name.to_owned(),
+        temporal_child_index: 0,
+        is_external: false,
+        desc: ResourceDesc::default(),
+        clear_value: ClearValue::default(),
+        all_accesses: AccessAttr::default(),
+        initial_access: AccessAttr::default(),
+        lifetime_begin: 0,
+        lifetime_end: 0,
+        alloc_requirement: GpuMemoryRequirement::default(),
+        alloc_placement: HeapPlacement::default(),
+        runtime_resource: RuntimeResource::null()
+    }
+}
+
+fn transition(cmd_index: u32, resource_index: u32, next_access: AccessFlags) -> CmdDiagnostic {
+    CmdDiagnostic::Transition {
+        cmd_index,
+        prev_access: AccessAttr::default(),
+        next_access: access(next_access),
+        range: SubresourceRange::default(),
+        resource_index
+    }
+}
+
+fn snapshot() -> DiagnosticsSnapshot {
+    let mut snapshot = DiagnosticsSnapshot {
+        resources: vec![resource("color"), resource("depth")],
+        commands: vec![
+            transition(0, 0, AccessFlags::RENDER_TARGET),
+            transition(1, 1, AccessFlags::DEPTH_WRITE),
+            CmdDiagnostic::Node { cmd_index: 0 },
+            transition(2, 0, AccessFlags::SHADER_RESOURCE),
+            CmdDiagnostic::Node { cmd_index: 1 },
+        ],
+        batches: vec![CommandBatch {
+            queue_index: 0,
+            num_cmds: 5,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    snapshot.nodes.insert(
+        0,
+        NodeDiagnostic {
+            name: "draw_scene".to_owned(),
+            accesses: vec![
+                NodeResourceAccess {
+                    resource_index: 0,
+                    access: access(AccessFlags::RENDER_TARGET)
+                },
+                NodeResourceAccess {
+                    resource_index: 1,
+                    access: access(AccessFlags::DEPTH_WRITE)
+                },
+            ]
+        }
+    );
+    snapshot
+}
+
+#[test]
+fn node_labels_carry_name_and_accesses() {
+    let dot = snapshot().to_dot();
+
+    let expected = format!(
+        "cmd_2 [shape=box, fillcolor=lightblue, label=\"draw_scene\\n{}\"];",
+        label(AccessFlags::RENDER_TARGET | AccessFlags::DEPTH_WRITE)
+    );
+    assert!(dot.contains(&expected), "{dot}");
+    assert!(!dot.contains("label=\"cmd 0"), "{dot}");
+}
+
+#[test]
+fn nodes_have_edges_to_the_resources_they_access() {
+    let dot = snapshot().to_dot();
+
+    assert!(dot.contains(&format!("cmd_2 -> res_0 [label=\"{}\"];", label(AccessFlags::RENDER_TARGET))), "{dot}");
+    assert!(dot.contains(&format!("cmd_2 -> res_1 [label=\"{}\"];", label(AccessFlags::DEPTH_WRITE))), "{dot}");
+}
+
+#[test]
+fn unrecorded_nodes_take_accesses_from_preceding_transitions() {
+    let dot = snapshot().to_dot();
+
+    let shader_resource = label(AccessFlags::SHADER_RESOURCE);
+    assert!(dot.contains(&format!("cmd_4 [shape=box, fillcolor=lightblue, label=\"cmd 1\\n{shader_resource}\"];")), "{dot}");
+    assert!(dot.contains(&format!("cmd_4 -> res_0 [label=\"{shader_resource}\"];")), "{dot}");
+    assert!(!dot.contains("cmd_4 -> res_1"), "{dot}");
+}
+
+#[test]
+fn snapshots_without_a_batch_layout_are_unscheduled() {
+    let dot = DiagnosticsSnapshot { batches: Vec::new(), ..snapshot() }.to_dot();
+
+    assert!(dot.contains("subgraph cluster_unscheduled"), "{dot}");
+    assert!(dot.contains("cmd_2 [shape=box, fillcolor=white, label=\"draw_scene\\n"), "{dot}");
+}
+
+fn null_runtime_device() -> Device {
+    unsafe {
+        Device::builder()
+            .build_with(|device_create_info| {
+                let create_info = NullRuntimeDeviceCreateInfo {
+                    device_create_info,
+                    runtime_create_info: ptr::null()
+                };
+                null_runtime_device_create(&create_info)
+            })
+            .unwrap()
+    }
+}
+
+fn buffer_desc(size: u32) -> ResourceDesc {
+    ResourceDesc {
+        type_: ResourceType::BUFFER,
+        temporal_layers: 1,
+        buffer_image: ResourceBufferImageDesc {
+            buffer: ResourceBufferDesc {
+                size_in_bytes_lo: size,
+                size_in_bytes_hi: 0
+            }
+        },
+        ..Default::default()
+    }
+}
+
+// Writes `resource` with a compute node and then reads it back, which makes RPS insert a transition between the two.
+fn build_write_read(builder: &mut GraphBuilder<'_>, resource: &str) -> RpsResult<()> {
+    let uav = AccessAttr {
+        access_flags: AccessFlags::UNORDERED_ACCESS,
+        access_stages: ShaderStage::CS
+    };
+    let srv = AccessAttr {
+        access_flags: AccessFlags::SHADER_RESOURCE,
+        access_stages: ShaderStage::CS
+    };
+    let write = builder.declare_node("write", NodeDeclFlags::COMPUTE, &[NodeParam::buffer("dst", uav)])?;
+    let read = builder.declare_node("read", NodeDeclFlags::COMPUTE, &[NodeParam::buffer("src", srv)])?;
+    let buffer = builder.declare_resource(resource, &buffer_desc(1024))?;
+
+    let noop = |_: &mut CmdContext<'_>| -> RpsResult<()> { Ok(()) };
+    builder.add_node(write, &[NodeArg::buffer(buffer.buffer_view())], noop)?;
+    builder.add_node(read, &[NodeArg::buffer(buffer.buffer_view())], noop)?;
+    Ok(())
+}
+
+#[test]
+fn diagnostics_decode_a_null_runtime_graph() {
+    let device = null_runtime_device();
+    let schedule_flags = ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION | ScheduleFlags::KEEP_PROGRAM_ORDER;
+    let mut render_graph = RenderGraph::builder()
+        .schedule_flags(schedule_flags)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+    let frame_update = FrameUpdate::new(0).schedule_flags(schedule_flags);
+    render_graph.update_with(&frame_update, |builder| build_write_read(builder, "scratch")).unwrap();
+
+    let snapshot = {
+        let diagnostics = render_graph.diagnostics(RenderGraphDiagnosticInfoFlags::DEFAULT).unwrap();
+
+        // The slices cover exactly the counts RPS reports.
+        let resources = diagnostics.resources().collect::<Vec<_>>();
+        assert_eq!(resources.len(), diagnostics.resource_infos().len());
+        assert!(resources.iter().any(|resource| resource.name == "scratch"));
+        assert_eq!(diagnostics.commands().len(), diagnostics.cmd_infos().len());
+        let batch_ranges = |batches: &[CommandBatch]| batches.iter().map(|batch| (batch.queue_index, batch.cmd_begin, batch.num_cmds)).collect::<Vec<_>>();
+        assert_eq!(batch_ranges(diagnostics.batches()), batch_ranges(render_graph.command_batches().unwrap()));
+
+        // Commands decode according to the union tag.
+        for (command, info) in diagnostics.commands().zip(diagnostics.cmd_infos()) {
+            assert_eq!(command.is_transition(), info.is_transition != 0);
+            assert_eq!(command.cmd_index(), info.cmd_index);
+            if let CmdDiagnostic::Transition { resource_index, .. } = command {
+                assert!((resource_index as usize) < resources.len());
+            }
+        }
+
+        let node_names = diagnostics
+            .commands()
+            .filter_map(|command| {
+                match command {
+                    CmdDiagnostic::Node { cmd_index } => diagnostics.node(cmd_index).map(|node| node.name.clone()),
+                    CmdDiagnostic::Transition { .. } => None
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(node_names, ["write", "read"]);
+
+        let scratch_index = resources.iter().position(|resource| resource.name == "scratch").unwrap() as u32;
+        assert!(diagnostics.commands().any(|command| {
+            matches!(command, CmdDiagnostic::Transition { resource_index, next_access, .. }
+                if resource_index == scratch_index && next_access.access_flags.contains(AccessFlags::SHADER_RESOURCE))
+        }));
+
+        diagnostics.snapshot()
+    };
+
+    // A snapshot owns its data, so it still describes the first frame after the graph has been rebuilt.
+    let num_commands = snapshot.commands.len();
+    render_graph.update_with(&FrameUpdate::new(1).schedule_flags(schedule_flags), |_| Ok(())).unwrap();
+
+    assert_eq!(snapshot.commands.len(), num_commands);
+    assert!(snapshot.resources.iter().any(|resource| resource.name == "scratch"));
+    assert_eq!(snapshot.nodes.values().filter(|node| node.name == "write" || node.name == "read").count(), 2);
+    assert!(snapshot.to_dot().contains("scratch"));
+
+    let diagnostics = render_graph.diagnostics(RenderGraphDiagnosticInfoFlags::DEFAULT).unwrap();
+    assert!(diagnostics.resources().all(|resource| resource.name != "scratch"));
+}
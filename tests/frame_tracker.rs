@@ -0,0 +1,104 @@
+use std::{sync::Arc, thread, time::Duration};
+
+use rps::{safe::FrameTracker, RpsError, GPU_COMPLETED_FRAME_INDEX_NONE, MAX_QUEUED_FRAMES};
+
+#[test]
+fn max_queued_frames_is_clamped() {
+    assert_eq!(FrameTracker::new(0).max_queued_frames(), 1);
+    assert_eq!(FrameTracker::new(MAX_QUEUED_FRAMES + 1).max_queued_frames(), MAX_QUEUED_FRAMES);
+    assert_eq!(FrameTracker::default().max_queued_frames(), MAX_QUEUED_FRAMES);
+}
+
+#[test]
+fn frames_wrap_around_the_queue() {
+    let tracker = FrameTracker::new(3);
+
+    for frame_index in 0..3 {
+        let frame_update = tracker.begin_frame().unwrap();
+        assert_eq!(frame_update.frame_index, frame_index);
+        assert_eq!(frame_update.gpu_completed_frame_index, GPU_COMPLETED_FRAME_INDEX_NONE);
+    }
+    assert_eq!(tracker.num_queued_frames(), 3);
+    assert!(matches!(tracker.begin_frame(), Err(RpsError::InvalidOperation(_))));
+
+    // Each completion frees exactly one slot, over many more frames than the queue holds.
+    for frame_index in 3..50 {
+        tracker.complete_frame(frame_index - 3).unwrap();
+        let frame_update = tracker.begin_frame().unwrap();
+        assert_eq!(frame_update.frame_index, frame_index);
+        assert_eq!(frame_update.gpu_completed_frame_index, frame_index - 3);
+        assert_eq!(tracker.num_queued_frames(), 3);
+        assert!(tracker.begin_frame().is_err());
+    }
+
+    tracker.complete_frame(49).unwrap();
+    assert_eq!(tracker.num_queued_frames(), 0);
+    assert_eq!(tracker.frame_index(), 50);
+}
+
+#[test]
+fn out_of_order_completion_keeps_the_latest_frame() {
+    let tracker = FrameTracker::new(4);
+    for _ in 0..4 {
+        tracker.begin_frame().unwrap();
+    }
+
+    tracker.complete_frame(2).unwrap();
+    assert_eq!(tracker.gpu_completed_frame_index(), 2);
+    assert_eq!(tracker.num_queued_frames(), 1);
+
+    tracker.complete_frame(0).unwrap();
+    tracker.complete_frame(1).unwrap();
+    assert_eq!(tracker.gpu_completed_frame_index(), 2);
+    assert_eq!(tracker.num_queued_frames(), 1);
+
+    assert!(matches!(tracker.complete_frame(4), Err(RpsError::IndexOutOfBounds(_))));
+    assert_eq!(tracker.gpu_completed_frame_index(), 2);
+}
+
+#[test]
+fn timeline_values_map_to_frames() {
+    let tracker = FrameTracker::new(2);
+    tracker.begin_frame().unwrap();
+    tracker.begin_frame().unwrap();
+
+    // Values before the first frame's value are ignored.
+    tracker.complete_timeline_value(99, 100).unwrap();
+    assert_eq!(tracker.gpu_completed_frame_index(), GPU_COMPLETED_FRAME_INDEX_NONE);
+
+    tracker.complete_timeline_value(101, 100).unwrap();
+    assert_eq!(tracker.gpu_completed_frame_index(), 1);
+    assert_eq!(tracker.num_queued_frames(), 0);
+}
+
+#[test]
+fn blocking_begin_waits_for_completion() {
+    let tracker = Arc::new(FrameTracker::new(1));
+    tracker.begin_frame().unwrap();
+    assert!(tracker.begin_frame_timeout(Duration::from_millis(10)).is_err());
+
+    let completer = {
+        let tracker = tracker.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tracker.complete_frame(0).unwrap();
+        })
+    };
+
+    let frame_update = tracker.begin_frame_blocking();
+    assert_eq!(frame_update.frame_index, 1);
+    assert_eq!(frame_update.gpu_completed_frame_index, 0);
+    completer.join().unwrap();
+}
+
+#[test]
+fn reset_starts_over() {
+    let tracker = FrameTracker::new(2);
+    tracker.begin_frame().unwrap();
+    tracker.complete_frame(0).unwrap();
+    tracker.reset();
+
+    assert_eq!(tracker.frame_index(), 0);
+    assert_eq!(tracker.gpu_completed_frame_index(), GPU_COMPLETED_FRAME_INDEX_NONE);
+    assert_eq!(tracker.begin_frame().unwrap().frame_index, 0);
+}
@@ -0,0 +1,92 @@
+use std::{
+    ptr, slice, str,
+    sync::{Arc, Mutex}
+};
+
+use rps::{
+    null_runtime_device_create,
+    safe::{CmdContext, Device, FrameUpdate, GraphBuilder, NodeArg, NodeParam, RenderGraph},
+    NodeDeclFlags, NullRuntimeDeviceCreateInfo, RenderGraphRecordCommandInfo, RenderGraphSignatureDesc, RpsResult, ScheduleFlags
+};
+
+fn null_runtime_device() -> Device {
+    unsafe {
+        Device::builder()
+            .build_with(|device_create_info| {
+                let create_info = NullRuntimeDeviceCreateInfo {
+                    device_create_info,
+                    runtime_create_info: ptr::null()
+                };
+                null_runtime_device_create(&create_info)
+            })
+            .unwrap()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Recorded {
+    value: u32,
+    values: Vec<u32>,
+    text: String
+}
+
+#[test]
+fn arena_data_survives_until_recording() {
+    let device = null_runtime_device();
+    let mut render_graph = RenderGraph::builder()
+        .schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let frame_update = FrameUpdate::new(0).schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION);
+    render_graph
+        .update_with(&frame_update, |builder: &mut GraphBuilder<'_>| -> RpsResult<()> {
+            let node = builder.declare_node("arena", NodeDeclFlags::COMPUTE, &[NodeParam::value::<u32>("value")])?;
+
+            let value = builder.alloc_value(42u32)?;
+            let values = builder.alloc_slice(&[1u32, 2, 3, 4])?;
+            let text = builder.alloc_str("arena text")?;
+            let c_text = builder.alloc_c_str("arena c text")?;
+            assert_eq!(c_text.to_str().unwrap(), "arena c text");
+
+            let values = (values.as_ptr() as usize, values.len());
+            let text = (text.as_ptr() as usize, text.len());
+            let recorded = recorded.clone();
+            builder.add_node(node, &[NodeArg::value(value)], move |cmd: &mut CmdContext<'_>| -> RpsResult<()> {
+                let values = unsafe { slice::from_raw_parts(values.0 as *const u32, values.1) };
+                let text = unsafe { str::from_utf8_unchecked(slice::from_raw_parts(text.0 as *const u8, text.1)) };
+                recorded.lock().unwrap().push(Recorded {
+                    value: *cmd.arg::<u32>(0)?,
+                    values: values.to_vec(),
+                    text: text.to_owned()
+                });
+                Ok(())
+            })?;
+            Ok(())
+        })
+        .unwrap();
+
+    let batch_layout = render_graph.batch_layout().unwrap();
+    assert!(batch_layout.num_cmd_batches > 0);
+    for batch in unsafe { slice::from_raw_parts(batch_layout.cmd_batches, batch_layout.num_cmd_batches as _) } {
+        unsafe {
+            render_graph
+                .record_commands(&RenderGraphRecordCommandInfo {
+                    cmd_begin_index: batch.cmd_begin,
+                    num_cmds: batch.num_cmds,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+    }
+
+    assert_eq!(
+        *recorded.lock().unwrap(),
+        [Recorded {
+            value: 42,
+            values: vec![1, 2, 3, 4],
+            text: "arena text".to_owned()
+        }]
+    );
+}
@@ -0,0 +1,82 @@
+use std::{env, ffi::CStr, fs, process, sync::Arc};
+
+use rps::{
+    safe::{CmdContext, Device, HotReloadGraph, Program, RenderGraph, RpslModuleSource},
+    NodeDesc, ProgramCreateInfo, RenderGraphSignatureDesc, RpsError, RpsResult
+};
+
+fn program<'a>(device: &'a Device, nodes: &[&CStr]) -> Program<'a> {
+    let node_descs = nodes
+        .iter()
+        .map(|name| {
+            NodeDesc {
+                name: name.as_ptr(),
+                ..Default::default()
+            }
+        })
+        .collect::<Vec<_>>();
+    let signature_desc = RenderGraphSignatureDesc {
+        num_node_descs: node_descs.len() as _,
+        node_descs: node_descs.as_ptr(),
+        ..Default::default()
+    };
+
+    unsafe {
+        Program::with_create_info(
+            device,
+            &ProgramCreateInfo {
+                signature_desc: &signature_desc,
+                ..Default::default()
+            }
+        )
+    }
+    .unwrap()
+}
+
+fn bind(program: &mut Program<'_>, name: &str, alive: &Arc<()>) {
+    let alive = alive.clone();
+    program
+        .bind_node(name, move |_: &mut CmdContext<'_>| -> RpsResult<()> {
+            let _ = &alive;
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn reload_rebinds_declared_nodes_and_reports_stale_ones() {
+    let device = Device::builder().build().unwrap();
+    let alive = Arc::new(());
+
+    let mut previous = program(&device, &[c"blur", c"tonemap"]);
+    bind(&mut previous, "blur", &alive);
+    bind(&mut previous, "tonemap", &alive);
+    assert_eq!(Arc::strong_count(&alive), 3);
+
+    let mut next = program(&device, &[c"blur", c"bloom"]);
+    assert_eq!(previous.copy_bindings_to(&mut next).unwrap(), ["tonemap"]);
+    assert!(next.is_node_bound("blur"));
+    assert_eq!(next.unbound_nodes(), ["bloom"]);
+
+    // The rebound closure is shared, so it outlives the program it was first bound to.
+    drop(previous);
+    assert_eq!(Arc::strong_count(&alive), 2);
+    drop(next);
+    assert_eq!(Arc::strong_count(&alive), 1);
+}
+
+#[test]
+fn failed_dynamic_library_load_removes_shadow_copy() {
+    let device = Device::builder().build().unwrap();
+    let dir = env::temp_dir().join(format!("rps-hot-reload-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("hot_reload_broken.so");
+    fs::write(&path, b"not a shared library").unwrap();
+
+    let result = HotReloadGraph::new(&device, RpslModuleSource::DynamicLibrary { path: &path }, "hot_reload_broken", "main", RenderGraph::builder());
+    assert!(matches!(result, Err(RpsError::FileNotFound(_))));
+
+    let shadow_path = env::temp_dir().join(format!("hot_reload_broken-{}-0.so", process::id()));
+    assert!(!shadow_path.exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,156 @@
+#![cfg(target_os = "linux")]
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread
+};
+
+use rps::{jit_lib_path, jit_lib_path_from, JITLibrary, RpsError};
+
+const JIT_LIB_NAME: &str = "librps-jit.so";
+
+const STUB_SOURCE: &str = r#"
+#include <unistd.h>
+
+typedef struct RpsJITModule_T* RpsJITModule;
+
+int stub_startup_count = 0;
+int stub_shutdown_count = 0;
+int stub_failing_startups = 0;
+
+int RpsJITStartup(int argc, const char** args) {
+    usleep(50000);
+    __atomic_add_fetch(&stub_startup_count, 1, __ATOMIC_SEQ_CST);
+    if (stub_failing_startups > 0) { stub_failing_startups--; return -1; }
+    return 0;
+}
+void RpsJITShutdown(void) { stub_shutdown_count++; }
+int RpsJITLoad(const char* name, RpsJITModule* module) { *module = 0; return 0; }
+void RpsJITUnload(RpsJITModule module) {}
+int RpsJITGetEntryPoint(RpsJITModule module, const char* symbol_name, unsigned long long* entry_point) { *entry_point = 0; return 0; }
+"#;
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("rps-jit-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn build_stub(dir: &Path) -> PathBuf {
+    let source = dir.join("stub.c");
+    let library = dir.join(JIT_LIB_NAME);
+    fs::write(&source, STUB_SOURCE).unwrap();
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_owned()))
+        .args(["-shared", "-fPIC", "-o"])
+        .arg(&library)
+        .arg(&source)
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "failed to build the stub JIT library");
+
+    library
+}
+
+#[test]
+fn explicit_file_path() {
+    let dir = scratch_dir("explicit-file");
+    let library = build_stub(&dir);
+
+    assert_eq!(jit_lib_path(Some(library.clone())).unwrap(), library);
+    assert!(JITLibrary::new(Some(library)).is_ok());
+}
+
+#[test]
+fn explicit_directory_path() {
+    let dir = scratch_dir("explicit-dir");
+    let library = build_stub(&dir);
+
+    assert_eq!(jit_lib_path(Some(dir.clone())).unwrap(), library);
+    assert!(JITLibrary::new(Some(dir)).is_ok());
+}
+
+#[test]
+fn explicit_path_missing() {
+    let dir = scratch_dir("explicit-missing");
+    let missing = dir.join("missing").join(JIT_LIB_NAME);
+
+    assert!(matches!(jit_lib_path(Some(missing.clone())), Err(RpsError::FileNotFound(_))));
+    assert!(matches!(jit_lib_path(Some(dir.clone())), Err(RpsError::FileNotFound(_))));
+    assert!(matches!(JITLibrary::new(Some(missing)), Err(RpsError::FileNotFound(_))));
+}
+
+#[test]
+fn explicit_path_missing_entry_points() {
+    let dir = scratch_dir("missing-entry-points");
+    let library = dir.join(JIT_LIB_NAME);
+    fs::write(&library, b"not a shared library").unwrap();
+
+    assert!(JITLibrary::new(Some(library)).is_err());
+}
+
+#[test]
+fn env_exe_dir_and_system_fallback() {
+    let env_dir = scratch_dir("env");
+    let env_library = build_stub(&env_dir);
+    let exe_dir = scratch_dir("exe");
+    let empty_dir = scratch_dir("empty");
+
+    let lookup = |env_path: Option<&Path>, exe_dir: &Path| jit_lib_path_from(None, env_path.map(Into::into), Some(exe_dir.to_owned())).unwrap();
+
+    assert_eq!(lookup(Some(&env_dir), &empty_dir), env_library);
+    assert_eq!(lookup(Some(&env_library), &empty_dir), env_library);
+    assert_eq!(lookup(Some(&env_dir.join("missing")), &empty_dir), Path::new(JIT_LIB_NAME));
+    assert_eq!(lookup(Some(Path::new("")), &empty_dir), Path::new(JIT_LIB_NAME));
+
+    let exe_library = build_stub(&exe_dir);
+    assert_eq!(lookup(Some(&env_dir), &exe_dir), env_library);
+    assert_eq!(lookup(Some(&env_dir.join("missing")), &exe_dir), exe_library);
+    assert_eq!(lookup(None, &exe_dir), exe_library);
+    assert_eq!(jit_lib_path_from(None, None, None).unwrap(), Path::new(JIT_LIB_NAME));
+}
+
+#[test]
+fn startup_runs_once_and_stop_shuts_down() {
+    let dir = scratch_dir("startup");
+    let library_path = build_stub(&dir);
+    let counters = unsafe { libloading::Library::new(&library_path) }.unwrap();
+    let startup_count = unsafe { *counters.get::<*const i32>(b"stub_startup_count\0").unwrap() };
+    let shutdown_count = unsafe { *counters.get::<*const i32>(b"stub_shutdown_count\0").unwrap() };
+
+    let library = JITLibrary::new(Some(library_path)).unwrap();
+    thread::scope(|scope| {
+        let threads = (0..4).map(|_| scope.spawn(|| library.start(&[]))).collect::<Vec<_>>();
+        for thread in threads {
+            assert!(thread.join().unwrap().is_ok());
+            assert!(library.is_started());
+        }
+    });
+    assert_eq!(unsafe { *startup_count }, 1);
+
+    library.stop();
+    assert_eq!(unsafe { *shutdown_count }, 1);
+}
+
+#[test]
+fn failed_startup_can_be_retried() {
+    let dir = scratch_dir("startup-retry");
+    let library_path = build_stub(&dir);
+    let counters = unsafe { libloading::Library::new(&library_path) }.unwrap();
+    let failing_startups = unsafe { *counters.get::<*mut i32>(b"stub_failing_startups\0").unwrap() };
+    let shutdown_count = unsafe { *counters.get::<*const i32>(b"stub_shutdown_count\0").unwrap() };
+    unsafe { *failing_startups = 1 };
+
+    let library = JITLibrary::new(Some(library_path)).unwrap();
+    assert!(library.start(&[]).is_err());
+    assert!(!library.is_started());
+
+    assert!(library.start(&[]).is_ok());
+    assert!(library.is_started());
+
+    library.stop();
+    assert_eq!(unsafe { *shutdown_count }, 1);
+}
@@ -0,0 +1,65 @@
+use std::ffi::CStr;
+
+use rps::{
+    node,
+    safe::{CmdContext, RpsArg},
+    AccessFlags, NodeDeclFlags, ParamAttr, ParameterFlags, RpsResult, Semantic, ShaderStage
+};
+
+node! {
+    static BLUR: fn blur(src: rps::ImageView [shader_resource(ps)], dst: rps::ImageView [render_target] => render_target[1], radius: u32) => fn blur_adapter;
+    static COPY: [prefer_async] fn copy(src: [rps::BufferView; 2] [copy_src], dst: rps::BufferView [copy_dest]);
+}
+
+fn param_attr(desc: &rps::ParameterDesc) -> &ParamAttr {
+    unsafe { &*(desc.attr as *const ParamAttr) }
+}
+
+fn param_name(desc: &rps::ParameterDesc) -> &str {
+    unsafe { CStr::from_ptr(desc.name) }.to_str().unwrap()
+}
+
+#[test]
+fn node_desc_describes_params_and_access() {
+    assert_eq!(BLUR.name(), "blur");
+    assert_eq!(BLUR.desc().flags, NodeDeclFlags::GRAPHICS);
+
+    let params = BLUR.params();
+    assert_eq!(params.iter().map(param_name).collect::<Vec<_>>(), ["src", "dst", "radius"]);
+
+    let src = &params[0];
+    assert_eq!(src.type_info.id, <rps::ImageView as RpsArg>::TYPE_INFO.id);
+    assert_eq!(src.flags, ParameterFlags::RESOURCE);
+    assert_eq!(param_attr(src).access.access_flags, AccessFlags::SHADER_RESOURCE);
+    assert_eq!(param_attr(src).access.access_stages, ShaderStage::PS);
+
+    let dst = &params[1];
+    assert_eq!(param_attr(dst).access.access_flags, AccessFlags::RENDER_TARGET);
+    assert_eq!(param_attr(dst).semantic.semantic, Semantic::RENDER_TARGET);
+    assert_eq!(param_attr(dst).semantic.semantic_index, 1);
+
+    let radius = &params[2];
+    assert_eq!(radius.type_info.id, <u32 as RpsArg>::TYPE_INFO.id);
+    assert_eq!(radius.flags, ParameterFlags::NONE);
+    assert_eq!(param_attr(radius).access.access_flags, AccessFlags::UNKNOWN);
+    assert_eq!(param_attr(radius).semantic.semantic, Semantic::UNSPECIFIED);
+}
+
+#[test]
+fn node_desc_accepts_array_types_and_explicit_flags() {
+    assert_eq!(COPY.name(), "copy");
+    assert_eq!(COPY.desc().flags, NodeDeclFlags::COPY | NodeDeclFlags::PREFER_ASYNC);
+
+    let params = COPY.params();
+    assert_eq!(params[0].array_size, 2);
+    assert_eq!(param_attr(&params[0]).access.access_flags, AccessFlags::COPY_SRC);
+    assert_eq!(params[1].array_size, 0);
+}
+
+#[test]
+fn adapter_accepts_typed_callbacks() {
+    let _callback = blur_adapter(|_: &mut CmdContext<'_>, _src: &rps::ImageView, _dst: &rps::ImageView, radius: &u32| -> RpsResult<()> {
+        assert!(*radius > 0);
+        Ok(())
+    });
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "log")]
+
+use std::{
+    cell::Cell,
+    ffi::CString,
+    sync::{Mutex, Once},
+    thread::{self, ThreadId}
+};
+
+use rps::{safe::log_printer, DiagLogLevel};
+
+struct CapturingLogger {
+    records: Mutex<Vec<(ThreadId, log::Level, String)>>
+}
+
+thread_local! {
+    static LOGGING_ENABLED: Cell<bool> = const { Cell::new(true) };
+}
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == "rps" && LOGGING_ENABLED.get()
+    }
+
+    // Records are captured unconditionally so the tests can tell whether the printer checked `enabled` first.
+    fn log(&self, record: &log::Record) {
+        self.records.lock().unwrap().push((thread::current().id(), record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger { records: Mutex::new(Vec::new()) };
+
+fn print(level: DiagLogLevel, message: &str) -> Vec<(log::Level, String)> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+
+    let printer = log_printer(level);
+    let format = CString::new(message).unwrap();
+    // The messages contain no conversions, so no arguments are read from the (zeroed) va_list.
+    let mut va_list = [0u64; 4];
+    unsafe { printer.pfn_vprintf.unwrap()(printer.context, format.as_ptr(), va_list.as_mut_ptr().cast()) };
+
+    let thread_id = thread::current().id();
+    let mut records = LOGGER.records.lock().unwrap();
+    let (own, others) = records.drain(..).partition(|(id, ..)| *id == thread_id);
+    *records = others;
+    own.into_iter().map(|(_, level, message)| (level, message)).collect()
+}
+
+#[test]
+fn messages_use_the_configured_level() {
+    let cases = [
+        (DiagLogLevel::INFO, log::Level::Info),
+        (DiagLogLevel::WARNING, log::Level::Warn),
+        (DiagLogLevel::ERROR, log::Level::Error),
+        (DiagLogLevel::FATAL, log::Level::Error)
+    ];
+
+    for (level, expected) in cases {
+        assert_eq!(print(level, "compiled with 0 errors, Failed: 0\n"), [(expected, "compiled with 0 errors, Failed: 0".to_owned())]);
+    }
+}
+
+#[test]
+fn long_messages_are_truncated() {
+    let message = "x".repeat(10_000);
+    let records = print(DiagLogLevel::INFO, &message);
+
+    assert_eq!(records.len(), 1);
+    let (level, logged) = &records[0];
+    assert_eq!(*level, log::Level::Info);
+    assert_eq!(logged.len(), 4095 + "...".len());
+    assert!(logged.starts_with(&message[..4095]));
+    assert!(logged.ends_with("x..."));
+}
+
+#[test]
+fn disabled_levels_are_not_formatted() {
+    LOGGING_ENABLED.set(false);
+    let records = print(DiagLogLevel::ERROR, "skipped");
+    LOGGING_ENABLED.set(true);
+
+    assert!(records.is_empty());
+}
+
+#[test]
+fn empty_messages_are_dropped() {
+    assert!(print(DiagLogLevel::WARNING, " \n").is_empty());
+}
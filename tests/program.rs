@@ -0,0 +1,131 @@
+use std::{ffi::CStr, ptr, slice};
+
+use rps::{
+    null_runtime_device_create,
+    safe::{CmdContext, Device, FrameUpdate, GraphBuilder, Program, RenderGraph},
+    ErrorContext, NodeDeclFlags, NodeDesc, NullRuntimeDeviceCreateInfo, ProgramCreateInfo, RenderGraphRecordCommandInfo, RenderGraphSignatureDesc, RpsError, RpsResult, ScheduleFlags
+};
+
+fn null_runtime_device() -> Device {
+    unsafe {
+        Device::builder()
+            .build_with(|device_create_info| {
+                let create_info = NullRuntimeDeviceCreateInfo {
+                    device_create_info,
+                    runtime_create_info: ptr::null()
+                };
+                null_runtime_device_create(&create_info)
+            })
+            .unwrap()
+    }
+}
+
+fn program<'a>(device: &'a Device, nodes: &[&CStr]) -> Program<'a> {
+    let node_descs = nodes
+        .iter()
+        .map(|name| {
+            NodeDesc {
+                name: name.as_ptr(),
+                ..Default::default()
+            }
+        })
+        .collect::<Vec<_>>();
+    let signature_desc = RenderGraphSignatureDesc {
+        num_node_descs: node_descs.len() as _,
+        node_descs: node_descs.as_ptr(),
+        ..Default::default()
+    };
+
+    unsafe {
+        Program::with_create_info(
+            device,
+            &ProgramCreateInfo {
+                signature_desc: &signature_desc,
+                ..Default::default()
+            }
+        )
+    }
+    .unwrap()
+}
+
+fn noop(_: &mut CmdContext<'_>) -> RpsResult<()> {
+    Ok(())
+}
+
+#[test]
+fn unbound_nodes_include_subprogram_nodes() {
+    let device = Device::builder().build().unwrap();
+
+    let unbound_subprogram = program(&device, &[c"shadow", c"ao"]);
+    let mut bound_subprogram = program(&device, &[c"bloom"]);
+    bound_subprogram.bind_node("bloom", noop).unwrap();
+
+    let mut main_entry = program(&device, &[c"geometry", c"lighting", c"post", c"present"]);
+    main_entry.bind_node("geometry", noop).unwrap();
+    main_entry.bind_node_subprogram("lighting", &unbound_subprogram).unwrap();
+    main_entry.bind_node_subprogram("post", &bound_subprogram).unwrap();
+
+    assert_eq!(main_entry.unbound_nodes(), ["lighting/shadow", "lighting/ao", "present"]);
+    assert!(matches!(main_entry.validate_bindings(), Err(RpsError::ValidationFailed(_))));
+
+    main_entry.bind_node("present", noop).unwrap();
+    let error = main_entry.validate_bindings().unwrap_err();
+    assert_eq!(error.name(), Some("lighting/shadow, lighting/ao"));
+}
+
+#[test]
+fn fully_bound_subprograms_validate() {
+    let device = Device::builder().build().unwrap();
+
+    let mut subprogram = program(&device, &[c"blur"]);
+    subprogram.bind_node("blur", noop).unwrap();
+
+    let mut main_entry = program(&device, &[c"post"]);
+    main_entry.bind_node_subprogram("post", &subprogram).unwrap();
+
+    assert!(main_entry.unbound_nodes().is_empty());
+    assert!(main_entry.validate_bindings().is_ok());
+}
+
+fn record_single_node(callback: impl FnMut(&mut CmdContext<'_>) -> RpsResult<()> + Send + 'static) -> RpsResult<()> {
+    let device = null_runtime_device();
+    let mut render_graph = RenderGraph::builder()
+        .schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+
+    let mut callback = Some(callback);
+    render_graph
+        .update_with(&FrameUpdate::new(0), |builder: &mut GraphBuilder<'_>| -> RpsResult<()> {
+            let node = builder.declare_node("node", NodeDeclFlags::COMPUTE, &[])?;
+            builder.add_node(node, &[], callback.take().unwrap())?;
+            Ok(())
+        })
+        .unwrap();
+
+    let batch_layout = render_graph.batch_layout().unwrap();
+    for batch in unsafe { slice::from_raw_parts(batch_layout.cmd_batches, batch_layout.num_cmd_batches as _) } {
+        unsafe {
+            render_graph.record_commands(&RenderGraphRecordCommandInfo {
+                cmd_begin_index: batch.cmd_begin,
+                num_cmds: batch.num_cmds,
+                ..Default::default()
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn callback_errors_are_reported_to_rps() {
+    assert!(record_single_node(noop).is_ok());
+
+    let result = record_single_node(|_| Err(RpsError::InvalidData(ErrorContext::new("node"))));
+    assert!(matches!(result, Err(RpsError::InvalidData(_))), "{result:?}");
+}
+
+#[test]
+fn callback_panics_are_caught_and_reported() {
+    let result = record_single_node(|_| panic!("node panicked"));
+    assert!(matches!(result, Err(RpsError::InternalError(_))), "{result:?}");
+}
@@ -0,0 +1,167 @@
+use std::{
+    ptr,
+    sync::{Arc, Mutex}
+};
+
+use rps::{
+    null_runtime_device_create,
+    safe::{CmdContext, CustomPhase, Device, FrameUpdate, GraphBuilder, NodeParam, RenderGraph},
+    NodeDeclFlags, NodeDesc, NullRuntimeDeviceCreateInfo, ParameterDesc, QueueFlags, RenderGraphSignatureDesc, RenderGraphUpdateInfo, RpsError, RpsResult, RuntimeCommandBuffer,
+    ScheduleFlags, TypeInfo
+};
+
+fn null_runtime_device() -> Device {
+    unsafe {
+        Device::builder()
+            .build_with(|device_create_info| {
+                let create_info = NullRuntimeDeviceCreateInfo {
+                    device_create_info,
+                    runtime_create_info: ptr::null()
+                };
+                null_runtime_device_create(&create_info)
+            })
+            .unwrap()
+    }
+}
+
+struct NoopPhase;
+
+impl CustomPhase for NoopPhase {
+    fn run(&mut self, _render_graph: rps::RenderGraph, _update_info: &RenderGraphUpdateInfo) -> RpsResult<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn builder_creates_graph_with_queues_and_heap_budgets() {
+    let device = null_runtime_device();
+    let render_graph = RenderGraph::builder()
+        .queues(&[QueueFlags::GRAPHICS | QueueFlags::COMPUTE | QueueFlags::COPY, QueueFlags::COMPUTE])
+        .heap_budget_mibs(&[64, 16])
+        .schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+
+    assert_ne!(render_graph.handle(), rps::RenderGraph::null());
+    assert!(render_graph.unbound_nodes().is_empty());
+}
+
+#[test]
+fn strict_bindings_reject_unbound_nodes() {
+    let device = null_runtime_device();
+    let node_descs = [NodeDesc {
+        name: c"present".as_ptr(),
+        ..Default::default()
+    }];
+    let signature_desc = RenderGraphSignatureDesc {
+        num_node_descs: node_descs.len() as _,
+        node_descs: node_descs.as_ptr(),
+        ..Default::default()
+    };
+
+    let lenient = RenderGraph::builder().build_with_signature(&device, &signature_desc).unwrap();
+    assert_eq!(lenient.unbound_nodes(), ["present"]);
+
+    let strict = RenderGraph::builder().strict_node_bindings(true);
+    assert!(matches!(strict.build_with_signature(&device, &signature_desc), Err(RpsError::ValidationFailed(_))));
+
+    let bound = strict.build_with_signature_and_bindings(&device, &signature_desc, |main_entry| {
+        main_entry.bind_node("present", |_: &mut CmdContext<'_>| -> RpsResult<()> { Ok(()) })
+    });
+    assert!(bound.is_ok());
+}
+
+#[test]
+fn phases_require_replacing_default_phases() {
+    let device = null_runtime_device();
+    let builder = RenderGraph::builder().phase(|| NoopPhase);
+
+    assert!(matches!(
+        builder.build_with_signature(&device, &RenderGraphSignatureDesc::default()),
+        Err(RpsError::InvalidOperation(_))
+    ));
+}
+
+#[test]
+fn update_passes_typed_args() {
+    let device = null_runtime_device();
+    let param_descs = [ParameterDesc {
+        type_info: TypeInfo::init_from_type_and_id::<u32>(rps::TypeId::UINT32),
+        name: c"exposure".as_ptr(),
+        ..Default::default()
+    }];
+    let signature_desc = RenderGraphSignatureDesc {
+        num_params: param_descs.len() as _,
+        param_descs: param_descs.as_ptr(),
+        ..Default::default()
+    };
+
+    let mut render_graph = RenderGraph::builder().build_with_signature(&device, &signature_desc).unwrap();
+    let exposure = 7u32;
+    render_graph.update(&FrameUpdate::new(0).arg(&exposure)).unwrap();
+    assert!(render_graph.command_batches().unwrap().is_empty());
+}
+
+#[test]
+fn update_and_record_each_frame() {
+    let device = null_runtime_device();
+    let mut render_graph = RenderGraph::builder()
+        .schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    for frame_index in 0..3u64 {
+        let frame_update = FrameUpdate::new(frame_index).schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION);
+        render_graph
+            .update_with(&frame_update, |builder: &mut GraphBuilder<'_>| -> RpsResult<()> {
+                let node = builder.declare_node("frame", NodeDeclFlags::COMPUTE, &[NodeParam::value::<u64>("frame_index")])?;
+                let frame_index = builder.alloc_value(frame_index)?;
+                let recorded = recorded.clone();
+                builder.add_node(node, &[rps::safe::NodeArg::value(frame_index)], move |cmd: &mut CmdContext<'_>| -> RpsResult<()> {
+                    recorded.lock().unwrap().push(*cmd.arg::<u64>(0)?);
+                    Ok(())
+                })?;
+                Ok(())
+            })
+            .unwrap();
+
+        assert!(!render_graph.command_batches().unwrap().is_empty());
+        unsafe { render_graph.record_all_commands(RuntimeCommandBuffer::null(), frame_index) }.unwrap();
+    }
+
+    assert_eq!(*recorded.lock().unwrap(), [0, 1, 2]);
+}
+
+#[test]
+fn failed_updates_keep_node_callbacks_alive() {
+    let device = null_runtime_device();
+    let mut render_graph = RenderGraph::builder()
+        .schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+
+    let alive = Arc::new(());
+    let add_node = |builder: &mut GraphBuilder<'_>, fail: bool| -> RpsResult<()> {
+        let node = builder.declare_node("node", NodeDeclFlags::COMPUTE, &[])?;
+        let alive = alive.clone();
+        builder.add_node(node, &[], move |_: &mut CmdContext<'_>| -> RpsResult<()> {
+            let _ = &alive;
+            Ok(())
+        })?;
+        match fail {
+            true => Err(RpsError::InvalidData(rps::ErrorContext::new("build"))),
+            false => Ok(())
+        }
+    };
+
+    let result = render_graph.update_with(&FrameUpdate::new(0), |builder| add_node(builder, true));
+    assert!(matches!(result, Err(RpsError::InvalidData(_))));
+    assert_eq!(Arc::strong_count(&alive), 2);
+
+    render_graph.update_with(&FrameUpdate::new(1), |builder| add_node(builder, false)).unwrap();
+    assert_eq!(Arc::strong_count(&alive), 2);
+
+    render_graph.update_with(&FrameUpdate::new(2), |_| Ok(())).unwrap();
+    assert_eq!(Arc::strong_count(&alive), 1);
+}
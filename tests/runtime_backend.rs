@@ -0,0 +1,60 @@
+use std::{
+    ffi::c_void,
+    ptr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc
+    }
+};
+
+use rps::{
+    safe::{Device, RuntimeBackend},
+    Allocator
+};
+
+struct DropCounter {
+    drops: Arc<AtomicUsize>
+}
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl RuntimeBackend for DropCounter {}
+
+unsafe extern "C" fn failing_alloc(_user_context: *mut c_void, _size: usize, _alignment: usize) -> *mut c_void {
+    ptr::null_mut()
+}
+
+unsafe extern "C" fn failing_realloc(_user_context: *mut c_void, _old_buffer: *mut c_void, _old_size: usize, _new_size: usize, _alignment: usize) -> *mut c_void {
+    ptr::null_mut()
+}
+
+unsafe extern "C" fn ignore_free(_user_context: *mut c_void, _buffer: *mut c_void) {}
+
+#[test]
+fn backend_is_dropped_with_the_device() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let device = Device::builder().build_with_backend(DropCounter { drops: drops.clone() }).unwrap();
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    drop(device);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn backend_is_dropped_once_when_device_creation_fails() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let allocator = Allocator {
+        pfn_alloc: Some(failing_alloc),
+        pfn_realloc: Some(failing_realloc),
+        pfn_free: Some(ignore_free),
+        context: ptr::null_mut()
+    };
+
+    let result = unsafe { Device::builder().allocator(allocator) }.build_with_backend(DropCounter { drops: drops.clone() });
+    assert!(result.is_err());
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,71 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf}
+};
+
+// rps-sys exports that are deliberately left without a safe wrapper. Every entry needs a reason.
+const ALLOW_LIST: &[(&str, &str)] = &[];
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn exported_functions() -> BTreeSet<String> {
+    let bindings_path = manifest_dir().join("rps-sys/gen/bindings.rs");
+    let bindings = fs::read_to_string(&bindings_path).unwrap_or_else(|error| panic!("failed to read {}: {error}", bindings_path.display()));
+
+    bindings
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix("pub fn "))
+        .map(|signature| signature.split(|c: char| !c.is_ascii_alphanumeric() && c != '_').next().unwrap_or_default())
+        .filter(|name| name.starts_with("rps"))
+        .map(str::to_owned)
+        .collect()
+}
+
+fn collect_sources(dir: &Path, sources: &mut String) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_sources(&path, sources);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            sources.push_str(&fs::read_to_string(&path).unwrap());
+        }
+    }
+}
+
+fn is_wrapped(sources: &str, name: &str) -> bool {
+    let needle = format!("sys::{name}");
+    sources
+        .match_indices(&needle)
+        .any(|(index, _)| !sources[index + needle.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_'))
+}
+
+#[test]
+fn every_sys_function_is_wrapped_or_allowed() {
+    let mut sources = String::new();
+    collect_sources(&manifest_dir().join("src"), &mut sources);
+
+    let exported = exported_functions();
+    assert!(!exported.is_empty(), "no rps* functions found in the generated bindings");
+
+    let unwrapped = exported
+        .iter()
+        .filter(|name| !is_wrapped(&sources, name) && !ALLOW_LIST.iter().any(|(allowed, _)| allowed == name))
+        .collect::<Vec<_>>();
+    assert!(unwrapped.is_empty(), "rps-sys functions without a safe wrapper: {unwrapped:?}");
+}
+
+#[test]
+fn allow_list_only_names_unwrapped_exports() {
+    let mut sources = String::new();
+    collect_sources(&manifest_dir().join("src"), &mut sources);
+
+    let exported = exported_functions();
+    for (name, reason) in ALLOW_LIST {
+        assert!(!reason.is_empty(), "{name} is allow-listed without a reason");
+        assert!(exported.contains(*name), "{name} is allow-listed but not exported by rps-sys");
+        assert!(!is_wrapped(&sources, name), "{name} is allow-listed but already wrapped");
+    }
+}
@@ -0,0 +1,122 @@
+use std::{env, fs, panic, path::PathBuf};
+
+use rps::safe::{is_update_golden_value, Trace, TraceEvent};
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name)
+}
+
+fn sample_trace() -> Trace {
+    Trace {
+        events: vec![
+            TraceEvent::CreateHeap {
+                heap: 1,
+                memory_type_index: 0,
+                size: 65536,
+                alignment: 256,
+                debug_name: Some("main heap".to_owned())
+            },
+            TraceEvent::CreateHeap {
+                heap: 2,
+                memory_type_index: 1,
+                size: 4096,
+                alignment: 16,
+                debug_name: None
+            },
+            TraceEvent::CreateResource {
+                resource: 3,
+                resource_id: 0,
+                resource_type: "image_2d",
+                temporal_layers: 2,
+                size: 8192,
+                alignment: 256
+            },
+            TraceEvent::SetDebugName {
+                resource: 3,
+                resource_type: "image_2d",
+                name: "back \"buffer\"\tcopy\n".to_owned()
+            },
+            TraceEvent::CreateNodeResources { node_tag: 7, num_args: 3 },
+            TraceEvent::DebugMarker {
+                mode: "begin",
+                text: Some("shadow pass é \u{1}".to_owned())
+            },
+            TraceEvent::NodeCallback {
+                name: "draw shadows".to_owned(),
+                user_tag: 7,
+                num_args: 3
+            },
+            TraceEvent::DebugMarker { mode: "end", text: None },
+            TraceEvent::DestroyNodeResources,
+            TraceEvent::DestroyResource {
+                resource: 3,
+                resource_type: "image_2d"
+            },
+            TraceEvent::DestroyHeap { heap: 2 },
+            TraceEvent::DestroyHeap { heap: 1 },
+        ]
+    }
+}
+
+#[test]
+fn trace_round_trips_through_text() {
+    let trace = sample_trace();
+    assert_eq!(trace.to_text().parse::<Trace>().unwrap(), trace);
+
+    for event in &trace.events {
+        assert_eq!(event.to_string().parse::<TraceEvent>().unwrap(), *event);
+    }
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+    for line in [
+        "",
+        "create_bridge heap=1",
+        "destroy_heap",
+        "destroy_heap heap=one",
+        "destroy_heap heap=1 extra=2",
+        "destroy_resource resource=1 type=texture",
+        "set_debug_name resource=1 type=buffer name=\"unterminated",
+        "node_callback name=\"draw\" user_tag=0"
+    ] {
+        assert!(line.parse::<TraceEvent>().is_err(), "{line:?} should not parse");
+    }
+
+    assert!("destroy_heap heap=1\nnot_an_event\n".parse::<Trace>().is_err());
+}
+
+#[test]
+fn trace_matches_checked_in_golden() {
+    let trace = sample_trace();
+    trace.assert_golden(golden_path("sample.trace"));
+
+    let golden = fs::read_to_string(golden_path("sample.trace")).unwrap();
+    assert_eq!(golden.parse::<Trace>().unwrap(), trace);
+}
+
+#[test]
+fn only_truthy_values_request_golden_updates() {
+    for value in ["0", "false", "no", "off", "", "2"] {
+        assert!(!is_update_golden_value(value), "{value:?} should not update golden files");
+    }
+
+    for value in ["1", "true", "YES", " on\n"] {
+        assert!(is_update_golden_value(value), "{value:?} should update golden files");
+    }
+}
+
+#[test]
+fn golden_files_are_only_written_when_updating() {
+    let path = env::temp_dir().join(format!("rps-trace-{}.trace", std::process::id()));
+
+    fs::write(&path, "destroy_heap heap=1\n").unwrap();
+    assert!(panic::catch_unwind(|| sample_trace().assert_or_update_golden(&path, false)).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "destroy_heap heap=1\n");
+
+    sample_trace().assert_or_update_golden(&path, true);
+    assert!(sample_trace().matches_golden(&path).unwrap());
+    sample_trace().assert_or_update_golden(&path, false);
+
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,81 @@
+use std::{path::PathBuf, slice};
+
+use rps::{
+    safe::{CmdContext, Device, FrameUpdate, GraphBuilder, NodeArg, NodeParam, RenderGraph, TraceEvent, TraceRecorder},
+    NodeDeclFlags, RenderGraphRecordCommandInfo, RenderGraphSignatureDesc, RpsResult, RuntimeCommandBuffer, ScheduleFlags
+};
+
+#[test]
+fn traced_nodes_appear_in_the_trace() {
+    let recorder = TraceRecorder::new();
+    let device = Device::builder().build_with_backend(recorder.backend()).unwrap();
+    let mut render_graph = RenderGraph::builder()
+        .schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+
+    let frame_update = FrameUpdate::new(0).schedule_flags(ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION);
+    render_graph
+        .update_with(&frame_update, |builder: &mut GraphBuilder<'_>| -> RpsResult<()> {
+            let node = builder.declare_node("traced", NodeDeclFlags::COMPUTE, &[])?;
+            builder.add_node_with_tag(node, 5, &[], recorder.traced(|_: &mut CmdContext<'_>| -> RpsResult<()> { Ok(()) }))?;
+            Ok(())
+        })
+        .unwrap();
+
+    let batch_layout = render_graph.batch_layout().unwrap();
+    for batch in unsafe { slice::from_raw_parts(batch_layout.cmd_batches, batch_layout.num_cmd_batches as _) } {
+        unsafe {
+            render_graph
+                .record_commands(&RenderGraphRecordCommandInfo {
+                    cmd_begin_index: batch.cmd_begin,
+                    num_cmds: batch.num_cmds,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+    }
+
+    let trace = recorder.take();
+    assert!(trace.events.contains(&TraceEvent::NodeCallback {
+        name: "traced".to_owned(),
+        user_tag: 5,
+        num_args: 0
+    }));
+    assert_eq!(trace.to_text().parse::<rps::safe::Trace>().unwrap(), trace);
+}
+
+#[test]
+fn recorded_graph_matches_golden() {
+    let recorder = TraceRecorder::new();
+    let device = Device::builder().build_with_backend(recorder.backend()).unwrap();
+    let schedule_flags = ScheduleFlags::DISABLE_DEAD_CODE_ELIMINATION | ScheduleFlags::KEEP_PROGRAM_ORDER;
+    let mut render_graph = RenderGraph::builder()
+        .schedule_flags(schedule_flags)
+        .build_with_signature(&device, &RenderGraphSignatureDesc::default())
+        .unwrap();
+
+    render_graph
+        .update_with(&FrameUpdate::new(0).schedule_flags(schedule_flags), |builder: &mut GraphBuilder<'_>| -> RpsResult<()> {
+            let clear = builder.declare_node("clear", NodeDeclFlags::COMPUTE, &[])?;
+            let blur = builder.declare_node("blur", NodeDeclFlags::COMPUTE, &[NodeParam::value::<u32>("radius"), NodeParam::value::<f32>("sigma")])?;
+            let present = builder.declare_node("present", NodeDeclFlags::GRAPHICS, &[NodeParam::value::<u32>("sync_interval")])?;
+
+            let radius = builder.alloc_value(4u32)?;
+            let sigma = builder.alloc_value(1.5f32)?;
+            let sync_interval = builder.alloc_value(1u32)?;
+            let noop = |_: &mut CmdContext<'_>| -> RpsResult<()> { Ok(()) };
+
+            builder.add_node_with_tag(clear, 1, &[], recorder.traced(noop))?;
+            builder.add_node_with_tag(blur, 2, &[NodeArg::value(radius), NodeArg::value(sigma)], recorder.traced(noop))?;
+            builder.add_node_with_tag(present, 3, &[NodeArg::value(sync_interval)], recorder.traced(noop))?;
+            Ok(())
+        })
+        .unwrap();
+    unsafe { render_graph.record_all_commands(RuntimeCommandBuffer::null(), 0) }.unwrap();
+
+    // Only the node callbacks are compared: heap and resource events depend on the runtime's allocation strategy.
+    let mut trace = recorder.take();
+    trace.events.retain(|event| matches!(event, TraceEvent::NodeCallback { .. }));
+    trace.assert_golden(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/render_graph_nodes.trace"));
+}